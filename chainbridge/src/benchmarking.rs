@@ -1,23 +1,576 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for chainbridge
 
 use super::*;
 
-#[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{
-    benchmarks,
-    whitelisted_caller,
+use crate::pallet::{NextPruneBlock, RejectedVotes, RelayerVotes, Votes};
+use crate::types::{
+    ChainId, ChainKind, DepositNonce, InboundLimit, ProposalStatus,
+    TransferLimit, VotePayload,
+};
+use crate::Pallet as ChainBridge;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::{
+    sp_runtime::{
+        traits::{Hash, One, Saturating, Zero},
+        transaction_validity::TransactionSource,
+        Perbill,
+    },
+    traits::{Hooks, ReservableCurrency, UnfilteredDispatchable},
+    unsigned::ValidateUnsigned,
+    weights::Weight,
 };
 use frame_system::RawOrigin;
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+const SEED: u32 = 0;
+
+/// Gives `who` enough free balance to reserve the relayer bond.
+fn fund<T: Config>(who: &T::AccountId) {
+    let amount = T::RelayerBond::get()
+        .saturating_add(T::Currency::minimum_balance())
+        .saturating_mul(2u32.into());
+    T::Currency::make_free_balance_be(who, amount);
+}
+
+/// Registers `n` relayers and returns their accounts.
+fn setup_relayers<T: Config>(n: u32) -> Vec<T::AccountId> {
+    (0..n)
+        .map(|i| {
+            let relayer: T::AccountId = account("relayer", i, SEED);
+            fund::<T>(&relayer);
+            ChainBridge::<T>::register_relayer(relayer.clone()).unwrap();
+            relayer
+        })
+        .collect()
+}
+
+/// Whitelists a source chain and registers a resource for proposals.
+fn setup_bridge<T: Config>() -> (ChainId, ResourceId) {
+    let src_id: ChainId = T::ChainId::get().wrapping_add(1);
+    let r_id = derive_resource_id(src_id, b"remark");
+    ChainBridge::<T>::whitelist(src_id).unwrap();
+    ChainBridge::<T>::register_resource(
+        r_id,
+        T::BenchmarkHelper::proposal_method(),
+    )
+    .unwrap();
+    (src_id, r_id)
+}
+
+/// Creates a proposal the proposal filter allows.
+fn make_proposal<T: Config>() -> <T as Config>::Proposal {
+    T::BenchmarkHelper::make_proposal()
+}
+
+/// Casts a vote in favour of `call` from every relayer in `relayers`.
+fn vote_for_all<T: Config>(
+    relayers: &[T::AccountId],
+    nonce: DepositNonce,
+    src_id: ChainId,
+    r_id: ResourceId,
+    call: &<T as Config>::Proposal,
+) {
+    for relayer in relayers {
+        ChainBridge::<T>::acknowledge_proposal(
+            RawOrigin::Signed(relayer.clone()).into(),
+            nonce,
+            src_id,
+            r_id,
+            Box::new(call.clone()),
+        )
+        .unwrap();
+    }
+}
+
+/// Casts a vote in favour of the call hash `prop_hash` from every relayer in
+/// `relayers`.
+fn vote_hash_for_all<T: Config>(
+    relayers: &[T::AccountId],
+    nonce: DepositNonce,
+    src_id: ChainId,
+    r_id: ResourceId,
+    prop_hash: T::Hash,
+) {
+    for relayer in relayers {
+        ChainBridge::<T>::acknowledge_proposal_hash(
+            RawOrigin::Signed(relayer.clone()).into(),
+            nonce,
+            src_id,
+            r_id,
+            prop_hash,
+        )
+        .unwrap();
+    }
+}
+
+/// Registers `n` relayers able to sign and returns their accounts.
+fn setup_signers<T: Config>(n: u32) -> Vec<T::AccountId> {
+    (0..n)
+        .map(|i| {
+            let relayer = T::BenchmarkHelper::create_signer(i);
+            fund::<T>(&relayer);
+            ChainBridge::<T>::register_relayer(relayer.clone()).unwrap();
+            relayer
+        })
+        .collect()
+}
+
+/// Signs the vote of `relayer` in favour of the call hash `prop_hash`.
+fn sign_vote<T: Config>(
+    relayer: T::AccountId,
+    nonce: DepositNonce,
+    src_id: ChainId,
+    r_id: ResourceId,
+    prop_hash: T::Hash,
+) -> (VotePayloadOf<T>, T::RelayerSignature) {
+    let payload = VotePayload {
+        relayer,
+        src_id,
+        nonce,
+        r_id,
+        prop_hash,
+        in_favour: true,
+    };
+    let signature = T::BenchmarkHelper::sign(
+        &payload.relayer,
+        &ChainBridge::<T>::vote_message(&payload),
+    );
+    (payload, signature)
+}
+
+/// Registers `n` relayers able to sign and returns their attestations of
+/// `call`, valid until block `valid_until`.
+fn setup_attestations<T: Config>(
+    n: u32,
+    nonce: DepositNonce,
+    src_id: ChainId,
+    r_id: ResourceId,
+    call: &<T as Config>::Proposal,
+    valid_until: T::BlockNumber,
+) -> Vec<(T::AccountId, T::RelayerSignature)> {
+    let prop_hash = T::Hashing::hash_of(call);
+    let relayers = setup_signers::<T>(n);
+    let epoch = ChainBridge::<T>::relayer_epoch();
+    relayers
+        .into_iter()
+        .map(|relayer| {
+            let payload = VotePayload {
+                relayer,
+                src_id,
+                nonce,
+                r_id,
+                prop_hash,
+                in_favour: true,
+            };
+            let message = ChainBridge::<T>::attestation_message(
+                &payload,
+                epoch,
+                valid_until,
+            );
+            let signature =
+                T::BenchmarkHelper::sign(&payload.relayer, &message);
+            (payload.relayer, signature)
+        })
+        .collect()
+}
 
 benchmarks! {
-    do_something {
-        let s in 0 .. 100;
-        let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), s)
+    set_threshold {
+        let threshold = 2;
+    }: _(RawOrigin::Root, threshold)
+    verify {
+        assert_eq!(ChainBridge::<T>::relayer_threshold(), threshold);
+    }
+
+    set_threshold_ratio {
+        let ratio = Perbill::from_percent(67);
+    }: _(RawOrigin::Root, ratio)
+    verify {
+        assert_eq!(ChainBridge::<T>::relayer_threshold_ratio(), Some(ratio));
+    }
+
+    set_resource {
+        let r_id = derive_resource_id(1, b"remark");
+        let method = vec![b'a'; T::MaxResourceMethodLen::get() as usize];
+    }: _(RawOrigin::Root, r_id, method)
+    verify {
+        assert!(ChainBridge::<T>::resource_exists(r_id));
+    }
+
+    remove_resource {
+        let r_id = derive_resource_id(1, b"remark");
+        ChainBridge::<T>::register_resource(r_id, T::BenchmarkHelper::proposal_method())?;
+    }: _(RawOrigin::Root, r_id)
+    verify {
+        assert!(!ChainBridge::<T>::resource_exists(r_id));
+    }
+
+    whitelist_chain {
+        let id: ChainId = T::ChainId::get().wrapping_add(1);
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(ChainBridge::<T>::chain_whitelisted(id));
+    }
+
+    register_chain {
+        let id: ChainId = T::ChainId::get().wrapping_add(1);
+        let name = vec![b'a'; T::MaxChainNameLen::get() as usize];
+    }: _(RawOrigin::Root, id, ChainKind::Evm, Some(20), name)
+    verify {
+        assert!(ChainBridge::<T>::chain_whitelisted(id));
+    }
+
+    disable_chain {
+        let id: ChainId = T::ChainId::get().wrapping_add(1);
+        ChainBridge::<T>::whitelist(id)?;
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(!ChainBridge::<T>::chain_whitelisted(id));
+    }
+
+    enable_chain {
+        let id: ChainId = T::ChainId::get().wrapping_add(1);
+        ChainBridge::<T>::whitelist(id)?;
+        ChainBridge::<T>::disable(id)?;
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(ChainBridge::<T>::chain_whitelisted(id));
+    }
+
+    add_relayer {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        fund::<T>(&relayer);
+    }: _(RawOrigin::Root, relayer.clone())
+    verify {
+        assert!(ChainBridge::<T>::is_relayer(&relayer));
+    }
+
+    remove_relayer {
+        let relayer = setup_relayers::<T>(1).remove(0);
+    }: _(RawOrigin::Root, relayer.clone())
+    verify {
+        assert!(!ChainBridge::<T>::is_relayer(&relayer));
+    }
+
+    set_fee {
+        let dest_id: ChainId = T::ChainId::get().wrapping_add(1);
+        let r_id = derive_resource_id(dest_id, b"remark");
+        let fee: BalanceOf<T> = 100u32.into();
+    }: _(RawOrigin::Root, dest_id, r_id, fee)
+    verify {
+        assert_eq!(ChainBridge::<T>::get_fee(dest_id, r_id), fee);
+    }
+
+    claim_rewards {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        let reward = T::Currency::minimum_balance() * 10u32.into();
+        <PendingRewards<T>>::insert(&relayer, reward);
+        T::Currency::make_free_balance_be(
+            &ChainBridge::<T>::fee_pot_account(),
+            reward * 2u32.into(),
+        );
+    }: _(RawOrigin::Signed(relayer.clone()))
+    verify {
+        assert!(ChainBridge::<T>::pending_rewards(&relayer).is_zero());
+    }
+
+    withdraw_unbonded {
+        let relayer: T::AccountId = account("relayer", 0, SEED);
+        fund::<T>(&relayer);
+        let amount = T::Currency::minimum_balance();
+        T::Currency::reserve(&relayer, amount)?;
+        let now = frame_system::Pallet::<T>::block_number();
+        <Unbonding<T>>::insert(&relayer, (amount, now));
+    }: _(RawOrigin::Signed(relayer.clone()))
+    verify {
+        assert!(ChainBridge::<T>::unbonding(&relayer).is_none());
+    }
+
+    // Removing a limit also clears the usage, which costs the most
+    set_transfer_limit {
+        let r_id = derive_resource_id(1, b"remark");
+        let limit = TransferLimit {
+            max_per_transfer: Some(100u32.into()),
+            max_per_window: Some(1_000u32.into()),
+            window: 10u32.into(),
+            max_deposits_per_block: Some(10),
+        };
+        ChainBridge::<T>::set_resource_transfer_limit(r_id, Some(limit))?;
+    }: _(RawOrigin::Root, r_id, None)
+    verify {
+        assert!(ChainBridge::<T>::transfer_limit(r_id).is_none());
+    }
+
+    // Removing a cap also clears the usage, which costs the most
+    set_inbound_limit {
+        let src_id: ChainId = T::ChainId::get().wrapping_add(1);
+        let r_id = derive_resource_id(src_id, b"remark");
+        let limit = InboundLimit {
+            max_per_window: 1_000u32.into(),
+            window: 10u32.into(),
+        };
+        ChainBridge::<T>::set_resource_inbound_limit(src_id, r_id, Some(limit))?;
+    }: _(RawOrigin::Root, src_id, r_id, None)
+    verify {
+        assert!(ChainBridge::<T>::inbound_limit(src_id, r_id).is_none());
+    }
+
+    retry_pending {
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let call = make_proposal::<T>();
+        let expiry = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::PendingLifetime::get());
+        let prop_hash = T::Hashing::hash_of(&call);
+        <PendingProposals<T>>::insert(src_id, (nonce, prop_hash), (r_id, expiry));
+        let caller: T::AccountId = account("caller", 0, SEED);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, Box::new(call))
+    verify {
+        assert!(ChainBridge::<T>::pending_proposal(src_id, (nonce, prop_hash)).is_none());
+    }
+
+    approve_pending {
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let call = make_proposal::<T>();
+        let expiry = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::PendingLifetime::get());
+        let prop_hash = T::Hashing::hash_of(&call);
+        <PendingProposals<T>>::insert(src_id, (nonce, prop_hash), (r_id, expiry));
+    }: _(RawOrigin::Root, nonce, src_id, Box::new(call))
+    verify {
+        assert!(ChainBridge::<T>::pending_proposal(src_id, (nonce, prop_hash)).is_none());
+    }
+
+    pause_bridge {
+    }: _(RawOrigin::Root)
+    verify {
+        assert!(ChainBridge::<T>::bridge_paused());
+    }
+
+    unpause_bridge {
+        <BridgePaused<T>>::put(true);
+    }: _(RawOrigin::Root)
+    verify {
+        assert!(!ChainBridge::<T>::bridge_paused());
+    }
+
+    pause_chain {
+        let id: ChainId = T::ChainId::get().wrapping_add(1);
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(ChainBridge::<T>::chain_paused(id));
+    }
+
+    unpause_chain {
+        let id: ChainId = T::ChainId::get().wrapping_add(1);
+        <PausedChains<T>>::insert(id, true);
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(!ChainBridge::<T>::chain_paused(id));
+    }
+
+    // The last vote reaches the threshold and executes the proposal.
+    acknowledge_proposal {
+        let v in 1 .. T::MaxRelayers::get();
+
+        let mut relayers = setup_relayers::<T>(v);
+        ChainBridge::<T>::set_relayer_threshold(v)?;
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let call = make_proposal::<T>();
+        let caller = relayers.pop().unwrap();
+        vote_for_all::<T>(&relayers, nonce, src_id, r_id, &call);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, r_id, Box::new(call.clone()))
+    verify {
+        let votes = ChainBridge::<T>::get_votes(src_id, (nonce, call)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Approved);
+    }
+
+    // The last vote makes the threshold unreachable and rejects the proposal.
+    reject_proposal {
+        let v in 1 .. T::MaxRelayers::get();
+
+        let mut relayers = setup_relayers::<T>(v);
+        ChainBridge::<T>::set_relayer_threshold(v)?;
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let call = make_proposal::<T>();
+        let caller = relayers.pop().unwrap();
+        vote_for_all::<T>(&relayers, nonce, src_id, r_id, &call);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, r_id, Box::new(call.clone()))
+    verify {
+        let votes = ChainBridge::<T>::get_votes(src_id, (nonce, call)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Rejected);
+    }
+
+    // Every relayer has voted, and lowering the threshold recorded for the
+    // proposal lets the evaluation execute it.
+    eval_vote_state {
+        let v in 1 .. T::MaxRelayers::get();
+
+        let relayers = setup_relayers::<T>(v);
+        // Out of reach, so the votes leave the proposal pending
+        <RelayerThreshold<T>>::put(v + 1);
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let call = make_proposal::<T>();
+        vote_for_all::<T>(&relayers, nonce, src_id, r_id, &call);
+        let prop_hash = T::Hashing::hash_of(&call);
+        <Votes<T>>::mutate(src_id, (nonce, prop_hash), |votes| {
+            if let Some(votes) = votes {
+                votes.threshold = v;
+            }
+        });
+        let caller = relayers[0].clone();
+    }: _(RawOrigin::Signed(caller), nonce, src_id, Box::new(call.clone()))
+    verify {
+        let votes = ChainBridge::<T>::get_votes(src_id, (nonce, call)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Approved);
+    }
+
+    // The last vote reaches the threshold and approves the proposal.
+    acknowledge_proposal_hash {
+        let v in 1 .. T::MaxRelayers::get();
+
+        let mut relayers = setup_relayers::<T>(v);
+        ChainBridge::<T>::set_relayer_threshold(v)?;
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let prop_hash = T::Hashing::hash_of(&make_proposal::<T>());
+        let caller = relayers.pop().unwrap();
+        vote_hash_for_all::<T>(&relayers, nonce, src_id, r_id, prop_hash);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, r_id, prop_hash)
+    verify {
+        assert_eq!(ChainBridge::<T>::awaiting_preimage(src_id, nonce), Some(prop_hash));
+    }
+
+    // The last vote makes the threshold unreachable and rejects the proposal.
+    reject_proposal_hash {
+        let v in 1 .. T::MaxRelayers::get();
+
+        let mut relayers = setup_relayers::<T>(v);
+        ChainBridge::<T>::set_relayer_threshold(v)?;
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let prop_hash = T::Hashing::hash_of(&make_proposal::<T>());
+        let caller = relayers.pop().unwrap();
+        vote_hash_for_all::<T>(&relayers, nonce, src_id, r_id, prop_hash);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, r_id, prop_hash)
+    verify {
+        let votes = ChainBridge::<T>::proposal_votes(src_id, (nonce, prop_hash)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Rejected);
+    }
+
+    // The signed vote is the last one reaching the threshold and approves
+    // the proposal. Validating the transaction checks the signature.
+    submit_vote_unsigned {
+        let v in 1 .. T::MaxRelayers::get();
+
+        let mut relayers = setup_signers::<T>(v);
+        ChainBridge::<T>::set_relayer_threshold(v)?;
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let prop_hash = T::Hashing::hash_of(&make_proposal::<T>());
+        let relayer = relayers.pop().unwrap();
+        vote_hash_for_all::<T>(&relayers, nonce, src_id, r_id, prop_hash);
+        let (payload, signature) =
+            sign_vote::<T>(relayer, nonce, src_id, r_id, prop_hash);
+        let call = Call::<T>::submit_vote_unsigned { payload, signature };
+    }: {
+        ChainBridge::<T>::validate_unsigned(TransactionSource::InBlock, &call)
+            .map_err(|_| "invalid vote")?;
+        call.dispatch_bypass_filter(RawOrigin::None.into())
+            .map_err(|e| e.error)?;
+    }
+    verify {
+        assert_eq!(ChainBridge::<T>::awaiting_preimage(src_id, nonce), Some(prop_hash));
+    }
+
+    execute_proposal {
+        let relayers = setup_relayers::<T>(1);
+        ChainBridge::<T>::set_relayer_threshold(1)?;
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let call = make_proposal::<T>();
+        let prop_hash = T::Hashing::hash_of(&call);
+        vote_hash_for_all::<T>(&relayers, nonce, src_id, r_id, prop_hash);
+        let caller: T::AccountId = account("caller", 0, SEED);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, Box::new(call))
+    verify {
+        assert!(ChainBridge::<T>::nonce_executed(src_id, nonce));
+    }
+
+    // Every attestation is valid and counts towards the threshold.
+    submit_attested_proposal {
+        let a in 1 .. T::MaxRelayers::get();
+
+        let (src_id, r_id) = setup_bridge::<T>();
+        let nonce: DepositNonce = 1;
+        let call = make_proposal::<T>();
+        let valid_until = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::ProposalLifetime::get());
+        let attestations: AttestationsOf<T> =
+            setup_attestations::<T>(a, nonce, src_id, r_id, &call, valid_until)
+                .try_into()
+                .map_err(|_| "too many attestations")?;
+        ChainBridge::<T>::set_relayer_threshold(a)?;
+        let caller: T::AccountId = account("caller", 0, SEED);
+    }: _(RawOrigin::Signed(caller), nonce, src_id, r_id, Box::new(call), valid_until, attestations)
+    verify {
+        assert!(ChainBridge::<T>::nonce_executed(src_id, nonce));
+    }
+
+    // Every proposal queued at the block expired unresolved, holding the
+    // votes of all relayers but one, for and against, and as many rejected
+    // unsigned votes. Removing it clears them all.
+    on_idle {
+        let n in 0 .. T::MaxPrunePerBlock::get();
+
+        let v = T::MaxRelayers::get();
+        let mut relayers = setup_relayers::<T>(v);
+        // One vote short of the threshold in favour, and one vote against
+        // which doesn't reject the proposals
+        ChainBridge::<T>::set_relayer_threshold(v.saturating_sub(1).max(1))?;
+        let (src_id, r_id) = setup_bridge::<T>();
+        let prop_hash = T::Hashing::hash_of(&make_proposal::<T>());
+        let against = relayers.pop().unwrap();
+        relayers.pop();
+        for nonce in 1 ..= n as DepositNonce {
+            vote_hash_for_all::<T>(&relayers, nonce, src_id, r_id, prop_hash);
+            ChainBridge::<T>::reject_proposal_hash(
+                RawOrigin::Signed(against.clone()).into(),
+                nonce,
+                src_id,
+                r_id,
+                prop_hash,
+            )
+            .map_err(|e| e.error)?;
+            for i in 0 .. v {
+                let payload_hash = T::Hashing::hash_of(&(nonce, i));
+                <RejectedVotes<T>>::insert((src_id, nonce), payload_hash, true);
+            }
+        }
+        let expiry = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::ProposalLifetime::get());
+        <NextPruneBlock<T>>::put(expiry);
+        let now = expiry.saturating_add(One::one());
+        frame_system::Pallet::<T>::set_block_number(now);
+    }: {
+        <ChainBridge<T> as Hooks<T::BlockNumber>>::on_idle(now, Weight::MAX);
+    }
     verify {
-        assert_eq!(Something::<T>::get(), Some(s));
+        for nonce in 1 ..= n as DepositNonce {
+            assert!(ChainBridge::<T>::proposal_votes(src_id, (nonce, prop_hash)).is_none());
+            for relayer in &relayers {
+                assert!(!<RelayerVotes<T>>::contains_key((src_id, nonce), relayer));
+            }
+            for i in 0 .. v {
+                let payload_hash = T::Hashing::hash_of(&(nonce, i));
+                assert!(!<RejectedVotes<T>>::contains_key((src_id, nonce), payload_hash));
+            }
+        }
     }
 
-    impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+    impl_benchmark_test_suite!(ChainBridge, crate::mock::new_test_ext(), crate::mock::Test);
 }
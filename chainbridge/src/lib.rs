@@ -1,13 +1,42 @@
 #![deny(warnings)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
+use frame_support::dispatch::DispatchResult;
 use frame_support::pallet_prelude::EnsureOrigin;
-use frame_support::sp_runtime::traits::AccountIdConversion;
-use frame_support::traits::Get;
+use frame_support::sp_runtime::traits::{
+    AccountIdConversion, DispatchInfoOf, Hash, SignedExtension,
+};
+use frame_support::sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionValidity, TransactionValidityError,
+    ValidTransaction,
+};
+use frame_support::traits::{
+    Currency, Get, GetCallMetadata, IsSubType, OnUnbalanced,
+};
 pub use pallet::*;
+use scale_info::TypeInfo;
+use sp_core::U256;
+use sp_std::{vec, vec::Vec};
 pub use types::ChainId;
+use types::DepositNonce;
 pub use types::ResourceId;
+pub use weights::WeightInfo;
+pub mod migrations;
 pub mod types;
+pub mod weights;
+
+/// Number of votes required for a proposal to execute, unless configured
+/// otherwise at genesis or through `set_threshold`.
+pub const DEFAULT_RELAYER_THRESHOLD: u32 = 1;
+
+/// Prefix of the message a relayer signs for a vote, so the signature
+/// cannot be passed off as one over another kind of message.
+pub const VOTE_SIGNING_CONTEXT: &[u8] = b"chainbridge:vote";
+
+/// Prefix of the message a relayer signs to attest a proposal, so an
+/// attestation and a vote cannot be passed off as one another.
+pub const ATTESTATION_SIGNING_CONTEXT: &[u8] = b"chainbridge:attestation";
 
 #[cfg(test)]
 mod mock;
@@ -22,17 +51,35 @@ mod benchmarking;
 pub mod pallet {
     use super::*;
     use crate::types::{
-        ChainId, DepositNonce, ProposalStatus, ProposalVotes, ResourceId,
+        ChainId, ChainInfo, ChainKind, DepositNonce, InboundLimit,
+        ProposalStatus, ProposalVotes, ResourceId, TransferLimit,
+        TransferUsage, VotePayload,
     };
     use codec::EncodeLike;
     use frame_support::{
-        dispatch::Dispatchable, inherent::*, pallet_prelude::*,
-        sp_runtime::traits::AccountIdConversion, weights::GetDispatchInfo,
-        PalletId,
+        dispatch::Dispatchable,
+        inherent::*,
+        pallet_prelude::*,
+        sp_runtime::{
+            traits::{
+                AccountIdConversion, Hash, IdentifyAccount, One, Saturating,
+                Verify, Zero,
+            },
+            transaction_validity::{
+                TransactionLongevity, TransactionPriority, TransactionSource,
+            },
+            PerThing, Perbill, TransactionOutcome,
+        },
+        storage::with_transaction,
+        traits::{
+            Contains, ExistenceRequirement, Imbalance, ReservableCurrency,
+            StorageVersion, WithdrawReasons,
+        },
+        weights::{extract_actual_weight, GetDispatchInfo, Pays, Weight},
+        PalletId, StorageHasher,
     };
     use frame_system::pallet_prelude::*;
     use scale_info::prelude::boxed::Box;
-    use sp_core::U256;
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -40,6 +87,8 @@ pub mod pallet {
             + IsType<<Self as frame_system::Config>::Event>;
         /// Origin used to administer the pallet
         type AdminOrigin: EnsureOrigin<Self::Origin>;
+        /// Origin allowed to pause and unpause the bridge, in addition to root
+        type PauseOrigin: EnsureOrigin<Self::Origin>;
         /// Proposed dispatchable call
         type Proposal: Parameter
             + Dispatchable<Origin = Self::Origin>
@@ -54,6 +103,17 @@ pub mod pallet {
         #[pallet::constant]
         type ProposalLifetime: Get<Self::BlockNumber>;
 
+        /// Number of blocks a complete proposal is kept after it is resolved,
+        /// and at least until its expiry, so the outcome can still be queried
+        /// before it is pruned.
+        #[pallet::constant]
+        type ProposalRetention: Get<Self::BlockNumber>;
+
+        /// Number of blocks a proposal parked by an inbound cap can be
+        /// released for. It is removed once it expires, freeing its nonce.
+        #[pallet::constant]
+        type PendingLifetime: Get<Self::BlockNumber>;
+
         /// Constant configuration parameter to store the module identifier for the pallet.
         ///
         /// The module identifier may be of the form ```PalletId(*b"chnbrdge")``` and set
@@ -61,28 +121,192 @@ pub mod pallet {
         // macro in the [`runtime/lib.rs`] file.
         #[pallet::constant]
         type PalletId: Get<PalletId>;
+
+        /// Maximum number of relayers, which also bounds the votes of a
+        /// proposal
+        #[pallet::constant]
+        type MaxRelayers: Get<u32>;
+
+        /// Maximum length of the method name a resource ID maps to
+        #[pallet::constant]
+        type MaxResourceMethodLen: Get<u32>;
+
+        /// Maximum length of the recipient of an outbound transfer
+        #[pallet::constant]
+        type MaxRecipientLen: Get<u32>;
+
+        /// Maximum length of the metadata of an outbound transfer
+        #[pallet::constant]
+        type MaxMetadataLen: Get<u32>;
+
+        /// Maximum length of the name of a chain
+        #[pallet::constant]
+        type MaxChainNameLen: Get<u32>;
+
+        /// Maximum number of proposals a relayer can vote on in one batch
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        /// Maximum number of proposals queued for pruning at a single block.
+        /// Proposals beyond it are queued at the following blocks.
+        #[pallet::constant]
+        type MaxPrunePerBlock: Get<u32>;
+
+        /// Currency in which bridge fees, rewards and relayer bonds are paid
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// Handler for the fees charged on outbound transfers
+        type FeeHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Reward credited to each relayer whose vote is on the winning side
+        /// of a resolved proposal, paid out of the fee pot. Zero disables
+        /// rewards.
+        #[pallet::constant]
+        type RelayerReward: Get<BalanceOf<Self>>;
+
+        /// Bond reserved from a relayer when it joins the relayer set. Zero
+        /// disables bonding.
+        #[pallet::constant]
+        type RelayerBond: Get<BalanceOf<Self>>;
+
+        /// Fraction of its bond a relayer loses when slashed
+        #[pallet::constant]
+        type SlashFraction: Get<Perbill>;
+
+        /// Handler for the funds slashed from relayer bonds
+        type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Number of blocks the bond of a removed relayer stays reserved
+        #[pallet::constant]
+        type UnbondingPeriod: Get<Self::BlockNumber>;
+
+        /// Handler for relayers voting for conflicting proposals
+        type OnEquivocation: OnEquivocation<Self::AccountId>;
+
+        /// Checks the call of a proposal is the method registered for its
+        /// resource
+        type ResourceCallMatcher: ResourceCallMatcher<Self::Proposal>;
+
+        /// Calls the bridge account may execute as the result of a proposal
+        type ProposalFilter: Contains<Self::Proposal>;
+
+        /// Signature relayers sign the votes they submit unsigned with
+        type RelayerSignature: Parameter + Verify<Signer = Self::RelayerPublic>;
+
+        /// Public key of a relayer, identifying its account
+        type RelayerPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Priority of unsigned relayer votes in the transaction pool
+        #[pallet::constant]
+        type UnsignedPriority: Get<TransactionPriority>;
+
+        /// Number of blocks unsigned relayer votes stay valid in the
+        /// transaction pool
+        #[pallet::constant]
+        type UnsignedLongevity: Get<TransactionLongevity>;
+
+        /// Creates relayers able to sign attestations, and proposals the
+        /// proposal filter allows, in benchmarks
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<
+            Self::AccountId,
+            Self::RelayerSignature,
+            Self::Proposal,
+        >;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
+    pub type BalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    /// Votes of a proposal, bounded by the maximum number of relayers
+    pub type ProposalVotesOf<T> = ProposalVotes<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        <T as Config>::MaxRelayers,
+    >;
+
+    /// Proposals voted on in a batch, given as
+    /// `(nonce, src_id, r_id, call)`
+    pub type ProposalBatchOf<T> = BoundedVec<
+        (
+            DepositNonce,
+            ChainId,
+            ResourceId,
+            Box<<T as Config>::Proposal>,
+        ),
+        <T as Config>::MaxBatchSize,
+    >;
+
+    /// Vote of a relayer submitted as an unsigned transaction
+    pub type VotePayloadOf<T> = VotePayload<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::Hash,
+    >;
+
+    /// Signatures of relayers attesting a proposal, given as
+    /// `(relayer, signature)`
+    pub type AttestationsOf<T> = BoundedVec<
+        (
+            <T as frame_system::Config>::AccountId,
+            <T as Config>::RelayerSignature,
+        ),
+        <T as Config>::MaxRelayers,
+    >;
+
+    /// Metadata of a whitelisted chain, bounded by the maximum name length
+    pub type ChainInfoOf<T> = ChainInfo<<T as Config>::MaxChainNameLen>;
+
+    /// The current storage version
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    #[pallet::generate_storage_info]
     pub struct Pallet<T>(_);
 
+    #[pallet::type_value]
+    pub fn DefaultRelayerThreshold() -> u32 {
+        DEFAULT_RELAYER_THRESHOLD
+    }
+
     #[pallet::storage]
     #[pallet::getter(fn relayer_threshold)]
     /// Number of votes required for a proposal to execute
-    pub type RelayerThreshold<T: Config> = StorageValue<_, u32, ValueQuery>;
+    pub type RelayerThreshold<T: Config> =
+        StorageValue<_, u32, ValueQuery, DefaultRelayerThreshold>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_threshold_ratio)]
+    /// Share of the relayer set whose votes are required for a proposal to
+    /// execute. Overrides `RelayerThreshold` when set.
+    pub type RelayerThresholdRatio<T: Config> =
+        StorageValue<_, Perbill, OptionQuery>;
 
     /// Utilized by the bridge software to map resource IDs to actual methods
     #[pallet::storage]
     #[pallet::getter(fn resources)]
-    pub type Resources<T: Config> =
-        StorageMap<_, Blake2_256, ResourceId, Vec<u8>, OptionQuery>;
+    pub type Resources<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        ResourceId,
+        BoundedVec<u8, T::MaxResourceMethodLen>,
+        OptionQuery,
+    >;
 
-    /// All whitelisted chains and their respective transaction counts
+    /// All whitelisted chains, with their outbound nonce and metadata
     #[pallet::storage]
     #[pallet::getter(fn chains)]
-    pub type ChainNonces<T: Config> =
-        StorageMap<_, Blake2_256, ChainId, Option<DepositNonce>, ValueQuery>;
+    pub type Chains<T: Config> =
+        StorageMap<_, Blake2_256, ChainId, ChainInfoOf<T>, OptionQuery>;
 
     /// Tracks current relayer set
     #[pallet::storage]
@@ -94,51 +318,369 @@ pub mod pallet {
     #[pallet::getter(fn relayer_count)]
     pub type RelayerCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+    /// Epoch of the relayer set, bumped whenever a relayer is added or
+    /// removed
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_epoch)]
+    pub type RelayerEpoch<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Epoch of the relayer set in which each relayer joined. Relayers of
+    /// the genesis set joined in epoch 0.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_since)]
+    pub type RelayerSince<T: Config> =
+        StorageMap<_, Blake2_256, T::AccountId, u32, ValueQuery>;
+
+    /// Whether the whole bridge is paused
+    #[pallet::storage]
+    #[pallet::getter(fn bridge_paused)]
+    pub type BridgePaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Chains whose transfers and proposals are paused
+    #[pallet::storage]
+    #[pallet::getter(fn chain_paused)]
+    pub type PausedChains<T: Config> =
+        StorageMap<_, Blake2_256, ChainId, bool, ValueQuery>;
+
+    /// Fee charged to the depositor of an outbound transfer, per destination
+    /// chain and resource
+    #[pallet::storage]
+    #[pallet::getter(fn get_fee)]
+    pub type BridgeFees<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Blake2_256,
+        ResourceId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Limits on the outbound transfers of each resource
+    #[pallet::storage]
+    #[pallet::getter(fn transfer_limit)]
+    pub type TransferLimits<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        ResourceId,
+        TransferLimit<T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Outbound transfer usage of each resource with a transfer limit, as of
+    /// its last transfer. See [`Pallet::current_usage`] for the usage at the
+    /// current block.
+    #[pallet::storage]
+    #[pallet::getter(fn transfer_usage)]
+    pub type TransferUsages<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        ResourceId,
+        TransferUsage<T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Caps on the value released by inbound proposals, per source chain and
+    /// resource
+    #[pallet::storage]
+    #[pallet::getter(fn inbound_limit)]
+    pub type InboundLimits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Blake2_256,
+        ResourceId,
+        InboundLimit<T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Value released by inbound proposals, per source chain and resource,
+    /// as of the last release
+    #[pallet::storage]
+    #[pallet::getter(fn inbound_usage)]
+    pub type InboundUsages<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Blake2_256,
+        ResourceId,
+        TransferUsage<T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Approved proposals held back by an inbound cap, keyed by their nonce
+    /// and the hash of their call, with the resource they were proposed
+    /// under and the block they expire at
+    #[pallet::storage]
+    #[pallet::getter(fn pending_proposal)]
+    pub type PendingProposals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Blake2_256,
+        (DepositNonce, T::Hash),
+        (ResourceId, T::BlockNumber),
+        OptionQuery,
+    >;
+
+    /// Source chain and resource of the proposal being executed, and whether
+    /// the inbound caps apply to it
+    #[pallet::storage]
+    pub(super) type InboundContext<T: Config> =
+        StorageValue<_, (ChainId, ResourceId, bool), OptionQuery>;
+
+    /// Rewards earned by relayers and not yet claimed
+    #[pallet::storage]
+    #[pallet::getter(fn pending_rewards)]
+    pub type PendingRewards<T: Config> =
+        StorageMap<_, Blake2_256, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Bond reserved from each relayer
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_bond)]
+    pub type RelayerBonds<T: Config> =
+        StorageMap<_, Blake2_256, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Bonds of removed relayers and the block from which they can be
+    /// withdrawn
+    #[pallet::storage]
+    #[pallet::getter(fn unbonding)]
+    pub type Unbonding<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        T::AccountId,
+        (BalanceOf<T>, T::BlockNumber),
+        OptionQuery,
+    >;
+
     /// All known proposals.
-    /// The key is the hash of the call and the deposit ID, to ensure it's unique.
+    /// The key is the deposit ID and the hash of the call, to ensure it's
+    /// unique.
     #[pallet::storage]
-    #[pallet::getter(fn get_votes)]
+    #[pallet::getter(fn proposal_votes)]
     pub(super) type Votes<T: Config> = StorageDoubleMap<
         _,
         Blake2_256,
         ChainId,
         Blake2_256,
-        (DepositNonce, T::Proposal),
-        ProposalVotes<T::AccountId, T::BlockNumber>,
+        (DepositNonce, T::Hash),
+        ProposalVotesOf<T>,
+        OptionQuery,
+    >;
+
+    /// Hash of the call of a proposal approved by votes on its hash alone,
+    /// awaiting `execute_proposal` with the call, per source chain and
+    /// inbound nonce
+    #[pallet::storage]
+    #[pallet::getter(fn awaiting_preimage)]
+    pub type AwaitingPreimage<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Blake2_256,
+        DepositNonce,
+        T::Hash,
+        OptionQuery,
+    >;
+
+    /// Hash of the call of the open or approved proposal using an inbound
+    /// nonce, per source chain
+    #[pallet::storage]
+    #[pallet::getter(fn in_flight_proposal)]
+    pub type InFlightProposals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Blake2_256,
+        DepositNonce,
+        T::Hash,
+        OptionQuery,
+    >;
+
+    /// Hash of the proposal each relayer voted for, per source chain and
+    /// inbound nonce
+    #[pallet::storage]
+    #[pallet::getter(fn relayer_vote)]
+    pub type RelayerVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        (ChainId, DepositNonce),
+        Blake2_256,
+        T::AccountId,
+        T::Hash,
+        OptionQuery,
+    >;
+
+    /// Unsigned votes that failed when dispatched, by the hash of their
+    /// payload, per source chain and inbound nonce. A rejected vote cannot
+    /// be submitted again.
+    #[pallet::storage]
+    #[pallet::getter(fn vote_rejected)]
+    pub type RejectedVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        (ChainId, DepositNonce),
+        Blake2_256,
+        T::Hash,
+        bool,
+        ValueQuery,
+    >;
+
+    /// Relayers caught voting for conflicting proposals, and the block it
+    /// happened at, per source chain and inbound nonce
+    #[pallet::storage]
+    #[pallet::getter(fn equivocation)]
+    pub type Equivocations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        T::AccountId,
+        Blake2_256,
+        (ChainId, DepositNonce),
+        T::BlockNumber,
         OptionQuery,
     >;
 
-    /*
+    /// Inbound nonces whose proposal has been executed, per source chain
+    #[pallet::storage]
+    #[pallet::getter(fn nonce_executed)]
+    pub type ExecutedNonces<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Blake2_256,
+        DepositNonce,
+        bool,
+        ValueQuery,
+    >;
+
+    /// Proposals executed before votes were keyed by the hash of the call, by
+    /// the hash of their nonce and call, per source chain. Their nonces were
+    /// not recorded, so proposals are checked against them when executed.
+    #[pallet::storage]
+    #[pallet::getter(fn legacy_executed)]
+    pub type LegacyExecutions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        ChainId,
+        Identity,
+        [u8; 32],
+        bool,
+        ValueQuery,
+    >;
+
+    /// Proposals to check for pruning, keyed by the block from which they can
+    /// be pruned.
+    #[pallet::storage]
+    pub(super) type PruneQueue<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        BoundedVec<(ChainId, DepositNonce, T::Hash), T::MaxPrunePerBlock>,
+        ValueQuery,
+    >;
+
+    /// The next block of the prune queue to be processed.
+    #[pallet::storage]
+    pub(super) type NextPruneBlock<T: Config> =
+        StorageValue<_, T::BlockNumber, OptionQuery>;
+
     // ------------------------------------------------------------------------
     // Pallet genesis configuration
     // ------------------------------------------------------------------------
 
-    // The genesis configuration type.
+    /// Bridge state the chain starts with, so a new network can relay
+    /// transfers without a round of admin calls.
     #[pallet::genesis_config]
-    pub struct GenesisConfig {}
+    pub struct GenesisConfig<T: Config> {
+        /// Initial relayer set
+        pub relayers: Vec<T::AccountId>,
+        /// Number of votes required for a proposal to execute
+        pub threshold: u32,
+        /// Whitelisted chains and the deposit nonce they start from
+        pub chains: Vec<(ChainId, DepositNonce)>,
+        /// Resource IDs and the methods they map to
+        pub resources: Vec<(ResourceId, Vec<u8>)>,
+    }
 
-    // The default value for the genesis config type.
     #[cfg(feature = "std")]
-    impl Default for GenesisConfig {
+    impl<T: Config> Default for GenesisConfig<T> {
         fn default() -> Self {
-            Self {}
+            Self {
+                relayers: vec![],
+                threshold: DEFAULT_RELAYER_THRESHOLD,
+                chains: vec![],
+                resources: vec![],
+            }
         }
     }
 
-    // The build of genesis for the pallet.
     #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig {
-        fn build(&self) {}
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            assert!(self.threshold > 0, "Relayer threshold cannot be 0");
+            assert!(
+                self.relayers.len() <= T::MaxRelayers::get() as usize,
+                "Too many relayers"
+            );
+            assert!(
+                self.relayers.is_empty()
+                    || self.threshold as usize <= self.relayers.len(),
+                "Relayer threshold is greater than the number of relayers"
+            );
+            <RelayerThreshold<T>>::put(self.threshold);
+
+            for relayer in self.relayers.iter() {
+                assert!(
+                    !<Relayers<T>>::get(relayer),
+                    "Relayer is listed more than once"
+                );
+                <Pallet<T>>::bond_relayer(relayer)
+                    .expect("Relayer cannot reserve the bond");
+                <Relayers<T>>::insert(relayer, true);
+            }
+            <RelayerCount<T>>::put(self.relayers.len() as u32);
+
+            for (id, nonce) in self.chains.iter() {
+                assert!(
+                    *id != T::ChainId::get(),
+                    "Cannot whitelist this chain"
+                );
+                assert!(
+                    !<Chains<T>>::contains_key(id),
+                    "Chain is listed more than once"
+                );
+                <Chains<T>>::insert(id, ChainInfoOf::<T>::new(*nonce));
+            }
+
+            for (id, method) in self.resources.iter() {
+                assert!(
+                    !<Resources<T>>::contains_key(id),
+                    "Resource is listed more than once"
+                );
+                let method: BoundedVec<_, _> = method
+                    .clone()
+                    .try_into()
+                    .expect("Resource method is too long");
+                <Resources<T>>::insert(id, method);
+            }
+        }
     }
-    */
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Vote threshold has changed (new_threshold)
         RelayerThresholdChanged(u32),
+        /// Vote threshold is now a share of the relayer set (ratio)
+        RelayerThresholdRatioChanged(Perbill),
         /// Chain now available for transfers (chain_id)
         ChainWhitelisted(ChainId),
+        /// Metadata of a chain has been set (chain_id, kind)
+        ChainRegistered(ChainId, ChainKind),
+        /// Chain no longer available for transfers and proposals (chain_id)
+        ChainDisabled(ChainId),
+        /// Disabled chain available for transfers again (chain_id)
+        ChainEnabled(ChainId),
         /// Relayer added to set
         RelayerAdded(T::AccountId),
         /// Relayer removed from set
@@ -168,6 +710,41 @@ pub mod pallet {
         ProposalSucceeded(ChainId, DepositNonce),
         /// Execution of call failed
         ProposalFailed(ChainId, DepositNonce),
+        /// Proposal expired before it was resolved, or while parked by an
+        /// inbound cap, and has been removed
+        ProposalExpired(ChainId, DepositNonce),
+        /// Fee of outbound transfers has changed (dest_id, resource_id, fee)
+        FeeChanged(ChainId, ResourceId, BalanceOf<T>),
+        /// Relayer claimed its rewards (relayer, amount)
+        RewardsClaimed(T::AccountId, BalanceOf<T>),
+        /// Bond of a relayer was slashed (relayer, amount)
+        RelayerSlashed(T::AccountId, BalanceOf<T>),
+        /// Bond of a removed relayer was released (relayer, amount)
+        BondWithdrawn(T::AccountId, BalanceOf<T>),
+        /// Relayer voted for conflicting proposals (relayer, src_id, nonce)
+        RelayerEquivocated(T::AccountId, ChainId, DepositNonce),
+        /// Outbound transfer limit of a resource has changed
+        TransferLimitChanged(ResourceId, Option<TransferLimit<T::BlockNumber>>),
+        /// Inbound cap of a resource from a source chain has changed
+        InboundLimitChanged(
+            ChainId,
+            ResourceId,
+            Option<InboundLimit<T::BlockNumber>>,
+        ),
+        /// Approved proposal exceeds an inbound cap and awaits execution
+        ProposalParked(ChainId, DepositNonce),
+        /// Bridge has been paused
+        BridgePaused,
+        /// Bridge has been unpaused
+        BridgeUnpaused,
+        /// Transfers and proposals of a chain have been paused
+        ChainPaused(ChainId),
+        /// Transfers and proposals of a chain have been unpaused
+        ChainUnpaused(ChainId),
+        /// Vote at this index of a batch was submitted
+        BatchVoteCompleted(u32),
+        /// Vote at this index of a batch failed (index, error)
+        BatchVoteFailed(u32, DispatchError),
     }
 
     #[pallet::error]
@@ -178,10 +755,21 @@ pub mod pallet {
         InvalidChainId,
         /// Relayer threshold cannot be 0
         InvalidThreshold,
+        /// Relayer threshold would exceed the number of relayers
+        ThresholdUnreachable,
         /// Interactions with this chain is not permitted
         ChainNotWhitelisted,
         /// Chain has already been enabled
         ChainAlreadyWhitelisted,
+        /// Chain has already been disabled
+        ChainAlreadyDisabled,
+        /// Chain is not disabled
+        ChainNotDisabled,
+        /// Chain name exceeds the maximum length
+        ChainNameTooLong,
+        /// Recipient does not have the address length of the destination
+        /// chain
+        InvalidRecipient,
         /// Resource ID provided isn't mapped to anything
         ResourceDoesNotExist,
         /// Relayer already in set
@@ -202,6 +790,80 @@ pub mod pallet {
         ProposalAlreadyComplete,
         /// Lifetime of proposal has been exceeded
         ProposalExpired,
+        /// Relayer set is full
+        TooManyRelayers,
+        /// Proposal has reached the maximum number of votes
+        TooManyVotes,
+        /// Resource method name exceeds the maximum length
+        ResourceMethodTooLong,
+        /// Recipient of a transfer exceeds the maximum length
+        RecipientTooLong,
+        /// Metadata of a transfer exceeds the maximum length
+        MetadataTooLong,
+        /// Depositor cannot pay the fee of the transfer
+        InsufficientFee,
+        /// Account has no rewards to claim
+        NoRewards,
+        /// Fee pot cannot pay out the rewards
+        InsufficientRewardFunds,
+        /// Relayer cannot reserve the bond
+        InsufficientBond,
+        /// Account has no bond being unbonded
+        NotUnbonding,
+        /// Unbonding period of the bond is not over
+        BondStillLocked,
+        /// Transfer amount exceeds the maximum of a single transfer
+        TransferAmountTooLarge,
+        /// Transfer would exceed the volume allowed within the window
+        TransferVolumeExceeded,
+        /// Maximum number of deposits for the resource in this block reached
+        TooManyDeposits,
+        /// Proposal would release more value than the inbound cap allows
+        InboundCapExceeded,
+        /// No parked proposal with these parameters was found
+        ProposalNotPending,
+        /// Bridge or chain is paused
+        BridgePaused,
+        /// Proposal with this nonce has already been executed
+        NonceAlreadyExecuted,
+        /// Another proposal with this nonce is in flight
+        NonceInUse,
+        /// Relayer has already equivocated on this nonce
+        AlreadyEquivocated,
+        /// Proposed call is not the method registered for the resource
+        CallDoesNotMatchResource,
+        /// Proposed call is not allowed by the proposal filter
+        ProposalFiltered,
+        /// No proposal approved by its hash awaits execution with this nonce
+        ProposalNotAwaitingPreimage,
+        /// Call does not match the hash of the approved proposal
+        ProposalHashMismatch,
+        /// Valid attestations do not meet the relayer threshold
+        InsufficientAttestations,
+        /// Relayer joined after the proposal was created
+        NotEpochMember,
+        /// Relayer already voted for another proposal with this nonce
+        ConflictingVote,
+        /// Resource differs from the one the proposal was created with
+        ProposalResourceMismatch,
+        /// Unsigned vote was already submitted and rejected
+        VoteRejected,
+        /// Batch does not contain any proposal
+        EmptyBatch,
+        /// Attestations have expired, or are valid for longer than a
+        /// proposal lifetime
+        InvalidAttestationPeriod,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::prune_proposals(now, remaining_weight)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::migrate::<T>()
+        }
     }
 
     #[pallet::call]
@@ -209,12 +871,14 @@ pub mod pallet {
         /// Sets the vote threshold for proposals.
         ///
         /// This threshold is used to determine how many votes are required
-        /// before a proposal is executed.
+        /// before a proposal is executed. It replaces a threshold set as a
+        /// share of the relayer set, and cannot exceed the number of
+        /// relayers unless the relayer set is empty.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::set_threshold())]
         pub fn set_threshold(
             origin: OriginFor<T>,
             threshold: u32,
@@ -225,12 +889,29 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Sets the vote threshold for proposals as a share of the relayer
+        /// set, rounded up to at least one vote. The number of votes
+        /// required follows the relayer set as it changes.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::set_threshold_ratio())]
+        pub fn set_threshold_ratio(
+            origin: OriginFor<T>,
+            ratio: Perbill,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_relayer_threshold_ratio(ratio)?;
+            Ok(())
+        }
+
         /// Stores a method name on chain under an associated resource ID.
         ///
         /// # <weight>
         /// - O(1) write
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::set_resource())]
         pub fn set_resource(
             origin: OriginFor<T>,
             id: ResourceId,
@@ -249,7 +930,7 @@ pub mod pallet {
         /// # <weight>
         /// - O(1) removeal
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::remove_resource())]
         pub fn remove_resource(
             origin: OriginFor<T>,
             id: ResourceId,
@@ -264,7 +945,7 @@ pub mod pallet {
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::whitelist_chain())]
         pub fn whitelist_chain(
             origin: OriginFor<T>,
             id: ChainId,
@@ -275,12 +956,62 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Whitelists a chain with its metadata, or updates the metadata of a
+        /// whitelisted chain. Its nonce and enabled flag are kept.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::register_chain())]
+        pub fn register_chain(
+            origin: OriginFor<T>,
+            id: ChainId,
+            kind: ChainKind,
+            recipient_len: Option<u32>,
+            name: Vec<u8>,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::register(id, kind, recipient_len, name)?;
+            Ok(())
+        }
+
+        /// Stops new deposits to and proposals from a whitelisted chain. The
+        /// nonce of the chain is kept.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::disable_chain())]
+        pub fn disable_chain(
+            origin: OriginFor<T>,
+            id: ChainId,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::disable(id)?;
+            Ok(())
+        }
+
+        /// Enables a disabled chain again, continuing from its last nonce.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and removal
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::enable_chain())]
+        pub fn enable_chain(
+            origin: OriginFor<T>,
+            id: ChainId,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::enable(id)?;
+            Ok(())
+        }
+
         /// Adds a new relayer to the relayer set.
         ///
         /// # <weight>
         /// - O(1) lookup and removal
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::add_relayer())]
         pub fn add_relayer(
             origin: OriginFor<T>,
             v: T::AccountId,
@@ -292,93 +1023,719 @@ pub mod pallet {
 
         /// Removes an existing relaye to the set.
         ///
+        /// The relayer cannot be removed if the remaining relayers could
+        /// not meet the vote threshold.
+        ///
         /// # <weight>
         /// - O(1) lookup and removal
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::remove_relayer())]
         pub fn remove_relayer(
             origin: OriginFor<T>,
             v: T::AccountId,
         ) -> DispatchResult {
             Self::ensure_admin(origin)?;
+            ensure!(Self::is_relayer(&v), Error::<T>::RelayerInvalid);
+            let remaining = Self::relayer_count().saturating_sub(1);
+            Self::ensure_threshold_reachable(
+                Self::threshold_for(remaining),
+                remaining,
+            )?;
             Self::unregister_relayer(v)?;
             Ok(())
         }
 
-        /// Commits a vote in favour of the provided proposal.
-        ///
-        /// If a proposal with the given nonce and source chain ID does not already exist,
-        /// it will be created with an initial vote in favour from the caller.
+        /// Sets the fee charged for outbound transfers of a resource to a
+        /// destination chain. A fee of zero makes the transfers free.
         ///
         /// # <weight>
-        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(1) write
         /// # </weight>
-        #[pallet::weight(10_000)]
-        pub fn acknowledge_proposal(
+        #[pallet::weight(T::WeightInfo::set_fee())]
+        pub fn set_fee(
+            origin: OriginFor<T>,
+            dest_id: ChainId,
+            r_id: ResourceId,
+            fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_bridge_fee(dest_id, r_id, fee)?;
+            Ok(())
+        }
+
+        /// Sets or, given `None`, removes the limits on outbound transfers of a
+        /// resource.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::set_transfer_limit())]
+        pub fn set_transfer_limit(
+            origin: OriginFor<T>,
+            r_id: ResourceId,
+            limit: Option<TransferLimit<T::BlockNumber>>,
+        ) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_resource_transfer_limit(r_id, limit)?;
+            Ok(())
+        }
+
+        /// Sets or, given `None`, removes the cap on the value released by
+        /// inbound proposals of a resource from a source chain.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::set_inbound_limit())]
+        pub fn set_inbound_limit(
             origin: OriginFor<T>,
-            nonce: DepositNonce,
             src_id: ChainId,
             r_id: ResourceId,
-            call: Box<<T as Config>::Proposal>,
+            limit: Option<InboundLimit<T::BlockNumber>>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            Self::ensure_admin(origin)?;
+            Self::set_resource_inbound_limit(src_id, r_id, limit)?;
+            Ok(())
+        }
+
+        /// Executes a proposal parked by an inbound cap, if the cap allows it
+        /// by now. Anyone can retry a parked proposal.
+        ///
+        /// Parked proposals are not retried automatically. One that is not
+        /// released within `PendingLifetime` blocks is removed and its nonce
+        /// freed, so relayers can propose the deposit again.
+        ///
+        /// # <weight>
+        /// - weight of proposed call
+        /// # </weight>
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                T::WeightInfo::retry_pending()
+                    .saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+                Pays::Yes,
+            )
+        })]
+        pub fn retry_pending(
+            origin: OriginFor<T>,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            call: Box<<T as Config>::Proposal>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
             ensure!(
                 Self::chain_whitelisted(src_id),
                 Error::<T>::ChainNotWhitelisted
             );
+            Self::ensure_not_paused(src_id)?;
+            let execution_weight =
+                Self::execute_pending(src_id, nonce, call, true)?;
+            Ok(Some(
+                T::WeightInfo::retry_pending().saturating_add(execution_weight),
+            )
+            .into())
+        }
+
+        /// Executes a proposal parked by an inbound cap, bypassing the cap.
+        ///
+        /// # <weight>
+        /// - weight of proposed call
+        /// # </weight>
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                T::WeightInfo::approve_pending()
+                    .saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+                Pays::Yes,
+            )
+        })]
+        pub fn approve_pending(
+            origin: OriginFor<T>,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            call: Box<<T as Config>::Proposal>,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_admin(origin)?;
+            let execution_weight =
+                Self::execute_pending(src_id, nonce, call, false)?;
+            Ok(Some(
+                T::WeightInfo::approve_pending()
+                    .saturating_add(execution_weight),
+            )
+            .into())
+        }
+
+        /// Pauses voting, proposal execution and outbound transfers on all
+        /// chains.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::pause_bridge())]
+        pub fn pause_bridge(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_pauser(origin)?;
+            <BridgePaused<T>>::put(true);
+            Self::deposit_event(Event::BridgePaused);
+            Ok(())
+        }
+
+        /// Lifts a pause of the whole bridge. Chains paused individually stay
+        /// paused.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::unpause_bridge())]
+        pub fn unpause_bridge(origin: OriginFor<T>) -> DispatchResult {
+            Self::ensure_pauser(origin)?;
+            <BridgePaused<T>>::kill();
+            Self::deposit_event(Event::BridgeUnpaused);
+            Ok(())
+        }
+
+        /// Pauses voting, proposal execution and outbound transfers for a
+        /// single chain.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::pause_chain())]
+        pub fn pause_chain(
+            origin: OriginFor<T>,
+            id: ChainId,
+        ) -> DispatchResult {
+            Self::ensure_pauser(origin)?;
+            <PausedChains<T>>::insert(id, true);
+            Self::deposit_event(Event::ChainPaused(id));
+            Ok(())
+        }
+
+        /// Lifts the pause of a single chain.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::unpause_chain())]
+        pub fn unpause_chain(
+            origin: OriginFor<T>,
+            id: ChainId,
+        ) -> DispatchResult {
+            Self::ensure_pauser(origin)?;
+            <PausedChains<T>>::remove(id);
+            Self::deposit_event(Event::ChainUnpaused(id));
+            Ok(())
+        }
+
+        /// Pays out the rewards the caller earned as a relayer from the fee
+        /// pot.
+        ///
+        /// # <weight>
+        /// - O(1) lookup, removal and transfer
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::claim_rewards())]
+        pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let amount = Self::pending_rewards(&who);
+            ensure!(!amount.is_zero(), Error::<T>::NoRewards);
+            T::Currency::transfer(
+                &Self::fee_pot_account(),
+                &who,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )
+            .map_err(|_| Error::<T>::InsufficientRewardFunds)?;
+            <PendingRewards<T>>::remove(&who);
+            Self::deposit_event(Event::RewardsClaimed(who, amount));
+            Ok(())
+        }
+
+        /// Releases the bond of a removed relayer once its unbonding period
+        /// is over.
+        ///
+        /// # <weight>
+        /// - O(1) lookup, removal and unreserve
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+        pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (amount, unlock_at) =
+                Self::unbonding(&who).ok_or(Error::<T>::NotUnbonding)?;
             ensure!(
-                Self::resource_exists(r_id),
-                Error::<T>::ResourceDoesNotExist
+                <frame_system::Pallet<T>>::block_number() >= unlock_at,
+                Error::<T>::BondStillLocked
             );
+            <Unbonding<T>>::remove(&who);
+            T::Currency::unreserve(&who, amount);
+            Self::deposit_event(Event::BondWithdrawn(who, amount));
+            Ok(())
+        }
 
-            Self::vote_for(who, nonce, src_id, call)
+        /// Commits a vote in favour of the provided proposal.
+        ///
+        /// If a proposal with the given nonce and source chain ID does not already exist,
+        /// it will be created with an initial vote in favour from the caller.
+        /// A vote that is committed pays no fee.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, refunded if the vote does not execute it
+        /// # </weight>
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                T::WeightInfo::acknowledge_proposal(T::MaxRelayers::get())
+                    .saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+                Pays::Yes,
+            )
+        })]
+        pub fn acknowledge_proposal(
+            origin: OriginFor<T>,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            r_id: ResourceId,
+            call: Box<<T as Config>::Proposal>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let execution_weight =
+                Self::acknowledge(who, nonce, src_id, r_id, call)?;
+            let weight =
+                T::WeightInfo::acknowledge_proposal(Self::relayer_count())
+                    .saturating_add(execution_weight);
+            Ok((Some(weight), Pays::No).into())
         }
 
-        /// Commits a vote against a provided proposal.
+        /// Commits a vote against a provided proposal. A vote that is
+        /// committed pays no fee.
+        ///
+        /// A vote against never executes the proposal, as proposals are
+        /// tallied against the threshold they were created with.
         ///
         /// # <weight>
-        /// - Fixed, since execution of proposal should not be included
+        /// - O(relayers) rewards and slashes when the vote resolves the proposal
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::reject_proposal(T::MaxRelayers::get()))]
         pub fn reject_proposal(
             origin: OriginFor<T>,
             nonce: DepositNonce,
             src_id: ChainId,
             r_id: ResourceId,
             call: Box<<T as Config>::Proposal>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(
+                Self::chain_whitelisted(src_id),
+                Error::<T>::ChainNotWhitelisted
+            );
+            Self::ensure_not_paused(src_id)?;
+            Self::ensure_call_matches_resource(r_id, &call)?;
+            let prop_hash = T::Hashing::hash_of(&call);
+            Self::vote_against(who, nonce, src_id, r_id, prop_hash, None)?;
+            let weight = T::WeightInfo::reject_proposal(Self::relayer_count());
+            Ok((Some(weight), Pays::No).into())
+        }
+
+        /// Commits a vote in favour of each of the given proposals, given as
+        /// `(nonce, src_id, r_id, call)`.
+        ///
+        /// A failing vote does not abort the batch. The outcome of each vote
+        /// is signalled as an event carrying its index in the batch. The
+        /// batch pays no fee only if all its votes are committed, so it
+        /// cannot be empty.
+        ///
+        /// # <weight>
+        /// - O(proposals) votes, each weighed as `acknowledge_proposal`
+        /// - weights of the proposed calls, refunded for the calls the votes
+        ///   do not execute
+        /// # </weight>
+        #[pallet::weight({
+            let vote_weight =
+                T::WeightInfo::acknowledge_proposal(T::MaxRelayers::get());
+            proposals.iter().fold(0, |weight: Weight, (_, _, _, call)| {
+                weight
+                    .saturating_add(vote_weight)
+                    .saturating_add(call.get_dispatch_info().weight)
+            })
+        })]
+        pub fn acknowledge_proposals_batch(
+            origin: OriginFor<T>,
+            proposals: ProposalBatchOf<T>,
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(!proposals.is_empty(), Error::<T>::EmptyBatch);
+
+            let mut weight =
+                T::WeightInfo::acknowledge_proposal(Self::relayer_count())
+                    .saturating_mul(proposals.len() as Weight);
+            let mut pays = Pays::No;
+            for (index, (nonce, src_id, r_id, call)) in
+                proposals.into_iter().enumerate()
+            {
+                // Discard the changes of a failing vote, as a failing
+                // extrinsic would, but keep a recorded equivocation
+                let conflicting: DispatchError =
+                    Error::<T>::ConflictingVote.into();
+                let result = with_transaction(|| {
+                    match Self::acknowledge(
+                        who.clone(),
+                        nonce,
+                        src_id,
+                        r_id,
+                        call,
+                    ) {
+                        Ok(weight) => TransactionOutcome::Commit(Ok(weight)),
+                        Err(e) if e == conflicting => {
+                            TransactionOutcome::Commit(Err(e))
+                        }
+                        Err(e) => TransactionOutcome::Rollback(Err(e)),
+                    }
+                });
+                match result {
+                    Ok(execution_weight) => {
+                        weight = weight.saturating_add(execution_weight);
+                        Self::deposit_event(Event::BatchVoteCompleted(
+                            index as u32,
+                        ));
+                    }
+                    Err(e) => {
+                        pays = Pays::Yes;
+                        Self::deposit_event(Event::BatchVoteFailed(
+                            index as u32,
+                            e,
+                        ));
+                    }
+                }
+            }
+            Ok((Some(weight), pays).into())
+        }
+
+        /// Commits a vote in favour of the proposal with the given call hash.
+        ///
+        /// A proposal approved by votes on its hash alone is executed once
+        /// anyone supplies the call through `execute_proposal`. A vote
+        /// carrying the call, through `acknowledge_proposal`, executes it
+        /// right away instead. A vote that is committed pays no fee.
+        ///
+        /// # <weight>
+        /// - O(relayers) rewards and slashes when the vote resolves the proposal
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::acknowledge_proposal_hash(
+            T::MaxRelayers::get()
+        ))]
+        pub fn acknowledge_proposal_hash(
+            origin: OriginFor<T>,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            r_id: ResourceId,
+            prop_hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_can_vote_hash(&who, src_id, r_id)?;
+            Self::vote_for(who, nonce, src_id, r_id, prop_hash, None)?;
+            let weight =
+                T::WeightInfo::acknowledge_proposal_hash(Self::relayer_count());
+            Ok((Some(weight), Pays::No).into())
+        }
+
+        /// Commits a vote against the proposal with the given call hash. A
+        /// vote that is committed pays no fee.
+        ///
+        /// # <weight>
+        /// - O(relayers) rewards and slashes when the vote resolves the proposal
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::reject_proposal_hash(
+            T::MaxRelayers::get()
+        ))]
+        pub fn reject_proposal_hash(
+            origin: OriginFor<T>,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            r_id: ResourceId,
+            prop_hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_can_vote_hash(&who, src_id, r_id)?;
+            Self::vote_against(who, nonce, src_id, r_id, prop_hash, None)?;
+            let weight =
+                T::WeightInfo::reject_proposal_hash(Self::relayer_count());
+            Ok((Some(weight), Pays::No).into())
+        }
+
+        /// Commits a vote on the proposal with the given call hash, signed by
+        /// the voting relayer and submitted as an unsigned transaction.
+        ///
+        /// The signature, over the message given by `vote_message`, is
+        /// checked when the transaction is validated, along with every check
+        /// of committing the vote. A vote failing when dispatched is
+        /// rejected for good.
+        ///
+        /// # <weight>
+        /// - signature check of the transaction validation
+        /// - O(relayers) rewards and slashes when the vote resolves the proposal
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::submit_vote_unsigned(
+            T::MaxRelayers::get()
+        ))]
+        pub fn submit_vote_unsigned(
+            origin: OriginFor<T>,
+            payload: VotePayloadOf<T>,
+            _signature: T::RelayerSignature,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let payload_hash = T::Hashing::hash_of(&payload);
+            let VotePayload {
+                relayer,
+                src_id,
+                nonce,
+                r_id,
+                prop_hash,
+                in_favour,
+            } = payload;
+            ensure!(
+                !Self::vote_rejected((src_id, nonce), payload_hash),
+                Error::<T>::VoteRejected
+            );
+            let result = Self::ensure_can_vote_hash(&relayer, src_id, r_id)
+                .and_then(|_| {
+                    if in_favour {
+                        Self::vote_for(
+                            relayer, nonce, src_id, r_id, prop_hash, None,
+                        )
+                    } else {
+                        Self::vote_against(
+                            relayer, nonce, src_id, r_id, prop_hash, None,
+                        )
+                    }
+                });
+            if result.is_err() {
+                <RejectedVotes<T>>::insert((src_id, nonce), payload_hash, true);
+            }
+            result?;
+            let weight =
+                T::WeightInfo::submit_vote_unsigned(Self::relayer_count());
+            Ok(Some(weight).into())
+        }
+
+        /// Executes a proposal attested by relayers, given as `(relayer,
+        /// signature)` pairs. Each relayer signs the vote in favour of the
+        /// proposal accepted by `submit_vote_unsigned`, bound to the current
+        /// relayer-set epoch and to the block `valid_until` until which the
+        /// attestation can be submitted. Anyone can submit the attestations.
+        ///
+        /// The proposal executes at once if the valid signatures of distinct
+        /// relayers meet the relayer threshold. Invalid signatures and
+        /// signatures of accounts outside the relayer set are not counted.
+        /// The nonce must not be in use by a proposal being voted on, and
+        /// attestations cannot be valid for longer than a proposal lifetime.
+        ///
+        /// # <weight>
+        /// - O(attestations) signature checks
+        /// - weight of proposed call
+        /// # </weight>
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                T::WeightInfo::submit_attested_proposal(
+                    attestations.len() as u32,
+                )
+                .saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+                Pays::Yes,
+            )
+        })]
+        pub fn submit_attested_proposal(
+            origin: OriginFor<T>,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            r_id: ResourceId,
+            call: Box<<T as Config>::Proposal>,
+            valid_until: T::BlockNumber,
+            attestations: AttestationsOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
             ensure!(
                 Self::chain_whitelisted(src_id),
                 Error::<T>::ChainNotWhitelisted
             );
+            Self::ensure_not_paused(src_id)?;
+            Self::ensure_call_matches_resource(r_id, &call)?;
             ensure!(
-                Self::resource_exists(r_id),
-                Error::<T>::ResourceDoesNotExist
+                T::ProposalFilter::contains(&call),
+                Error::<T>::ProposalFiltered
             );
-            Self::vote_against(who, nonce, src_id, call)
+            let weight = T::WeightInfo::submit_attested_proposal(
+                attestations.len() as u32,
+            );
+            let execution_weight = Self::execute_attested(
+                nonce,
+                src_id,
+                r_id,
+                call,
+                valid_until,
+                attestations,
+            )?;
+            Ok(Some(weight.saturating_add(execution_weight)).into())
         }
 
-        /// Evaluate the state of a proposal given the current vote threshold.
+        /// Evaluate the state of a proposal given the vote threshold recorded
+        /// when it was created.
         ///
         /// A proposal with enough votes will be either executed or cancelled, and the status
-        /// will be updated accordingly.
+        /// will be updated accordingly. Only the votes of relayers still in the
+        /// relayer set it was created with count.
         ///
         /// # <weight>
-        /// - weight of proposed call, regardless of whether execution is performed
+        /// - weight of proposed call, refunded if the evaluation does not execute it
         /// # </weight>
-        #[pallet::weight(10_000)]
+        #[pallet::weight({
+            let dispatch_info = prop.get_dispatch_info();
+            (
+                T::WeightInfo::eval_vote_state(T::MaxRelayers::get())
+                    .saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+                Pays::Yes,
+            )
+        })]
         pub fn eval_vote_state(
             origin: OriginFor<T>,
             nonce: DepositNonce,
             src_id: ChainId,
             prop: Box<<T as Config>::Proposal>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             ensure_signed(origin)?;
+            ensure!(
+                Self::chain_whitelisted(src_id),
+                Error::<T>::ChainNotWhitelisted
+            );
+            Self::ensure_not_paused(src_id)?;
+
+            let prop_hash = T::Hashing::hash_of(&prop);
+            let execution_weight = Self::try_resolve_proposal(
+                nonce,
+                src_id,
+                prop_hash,
+                Some(prop),
+            )?;
+            Ok(Some(
+                T::WeightInfo::eval_vote_state(Self::relayer_count())
+                    .saturating_add(execution_weight),
+            )
+            .into())
+        }
+
+        /// Executes a proposal approved by votes on its hash, given the call
+        /// the hash was computed from. Anyone can supply the call, which must
+        /// match the resource the proposal was voted on with.
+        ///
+        /// # <weight>
+        /// - weight of proposed call
+        /// # </weight>
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                T::WeightInfo::execute_proposal()
+                    .saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+                Pays::Yes,
+            )
+        })]
+        pub fn execute_proposal(
+            origin: OriginFor<T>,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            call: Box<<T as Config>::Proposal>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            ensure!(
+                Self::chain_whitelisted(src_id),
+                Error::<T>::ChainNotWhitelisted
+            );
+            Self::ensure_not_paused(src_id)?;
+            let execution_weight = Self::execute_preimage(src_id, nonce, call)?;
+            Ok(Some(
+                T::WeightInfo::execute_proposal()
+                    .saturating_add(execution_weight),
+            )
+            .into())
+        }
+    }
 
-            Self::try_resolve_proposal(nonce, src_id, prop)
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(
+            _source: TransactionSource,
+            call: &Self::Call,
+        ) -> TransactionValidity {
+            let (payload, signature) = match call {
+                Call::submit_vote_unsigned { payload, signature } => {
+                    (payload, signature)
+                }
+                _ => return InvalidTransaction::Call.into(),
+            };
+            let message = Self::vote_message(payload);
+            if !signature.verify(&message[..], &payload.relayer) {
+                return InvalidTransaction::BadProof.into();
+            }
+            if !Self::is_relayer(&payload.relayer) {
+                return InvalidTransaction::Custom(
+                    ValidityError::NotRelayer.into(),
+                )
+                .into();
+            }
+            let rejected = Self::vote_rejected(
+                (payload.src_id, payload.nonce),
+                T::Hashing::hash_of(payload),
+            );
+            if rejected
+                || Self::is_stale_vote(
+                    &payload.relayer,
+                    payload.src_id,
+                    payload.nonce,
+                    payload.prop_hash,
+                )
+            {
+                return InvalidTransaction::Stale.into();
+            }
+            // The checks of committing the vote when it is dispatched
+            let committable = Self::ensure_can_vote_hash(
+                &payload.relayer,
+                payload.src_id,
+                payload.r_id,
+            )
+            .and_then(|_| {
+                Self::votes_to_commit(
+                    &payload.relayer,
+                    payload.nonce,
+                    payload.src_id,
+                    payload.r_id,
+                    payload.prop_hash,
+                    payload.in_favour,
+                )
+            });
+            // A conflicting vote is let through to record the equivocation
+            let conflicting: DispatchError = Error::<T>::ConflictingVote.into();
+            if matches!(committable, Err(e) if e != conflicting) {
+                return InvalidTransaction::Custom(
+                    ValidityError::CannotCommit.into(),
+                )
+                .into();
+            }
+            ValidTransaction::with_tag_prefix("ChainBridgeVote")
+                .priority(T::UnsignedPriority::get())
+                .and_provides((
+                    &payload.relayer,
+                    payload.src_id,
+                    payload.nonce,
+                    payload.prop_hash,
+                ))
+                .longevity(T::UnsignedLongevity::get())
+                .propagate(true)
+                .build()
         }
     }
 
@@ -392,6 +1749,22 @@ pub mod pallet {
             Ok(())
         }
 
+        pub fn ensure_pauser(origin: OriginFor<T>) -> DispatchResult {
+            T::PauseOrigin::try_origin(origin)
+                .map(|_| ())
+                .or_else(ensure_root)?;
+            Ok(())
+        }
+
+        /// Checks neither the bridge nor the given chain is paused
+        pub fn ensure_not_paused(id: ChainId) -> DispatchResult {
+            ensure!(
+                !Self::bridge_paused() && !Self::chain_paused(id),
+                Error::<T>::BridgePaused
+            );
+            Ok(())
+        }
+
         /// Checks if who is a relayer
         pub fn is_relayer(who: &T::AccountId) -> bool {
             Self::relayers(who)
@@ -408,17 +1781,223 @@ pub mod pallet {
             Self::resources(id) != None
         }
 
-        /// Checks if a chain exists as a whitelisted destination
+        /// Checks the resource exists and maps to the proposed call
+        fn ensure_call_matches_resource(
+            r_id: ResourceId,
+            call: &T::Proposal,
+        ) -> DispatchResult {
+            let method = Self::resources(r_id)
+                .ok_or(Error::<T>::ResourceDoesNotExist)?;
+            ensure!(
+                T::ResourceCallMatcher::matches(&method, call),
+                Error::<T>::CallDoesNotMatchResource
+            );
+            Ok(())
+        }
+
+        /// Checks the voter is a relayer and the proposal of a vote on a call
+        /// hash comes from an open chain and uses a registered resource.
+        /// Whether the call matches the resource is checked on execution.
+        fn ensure_can_vote_hash(
+            who: &T::AccountId,
+            src_id: ChainId,
+            r_id: ResourceId,
+        ) -> DispatchResult {
+            ensure!(Self::is_relayer(who), Error::<T>::MustBeRelayer);
+            ensure!(
+                Self::chain_whitelisted(src_id),
+                Error::<T>::ChainNotWhitelisted
+            );
+            Self::ensure_not_paused(src_id)?;
+            ensure!(
+                Self::resource_exists(r_id),
+                Error::<T>::ResourceDoesNotExist
+            );
+            Ok(())
+        }
+
+        /// Checks whether a vote of the relayer on a proposal can no longer
+        /// be committed, because the relayer already voted on it, joined
+        /// after it was created, or it can no longer be voted on. A vote for
+        /// another proposal with the nonce is not stale, so the equivocation
+        /// is recorded when it is dispatched.
+        pub fn is_stale_vote(
+            who: &T::AccountId,
+            src_id: ChainId,
+            nonce: DepositNonce,
+            prop_hash: T::Hash,
+        ) -> bool {
+            let now = <frame_system::Pallet<T>>::block_number();
+            match Self::proposal_votes(src_id, (nonce, prop_hash)) {
+                Some(votes) => {
+                    votes.is_complete()
+                        || votes.is_expired(now)
+                        || votes.has_voted(who)
+                        || !Self::is_epoch_member(who, votes.epoch)
+                }
+                None => Self::nonce_executed(src_id, nonce),
+            }
+        }
+
+        /// Message a relayer signs for a vote, bound to the bridge of this
+        /// chain by the chain ID and the genesis hash
+        pub fn vote_message(payload: &VotePayloadOf<T>) -> Vec<u8> {
+            let genesis =
+                <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+            (VOTE_SIGNING_CONTEXT, T::ChainId::get(), genesis, payload).encode()
+        }
+
+        /// Message a relayer signs to attest a proposal, which binds the vote
+        /// to the relayer-set epoch and the block until which it is valid
+        pub fn attestation_message(
+            payload: &VotePayloadOf<T>,
+            epoch: u32,
+            valid_until: T::BlockNumber,
+        ) -> Vec<u8> {
+            let genesis =
+                <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+            (
+                ATTESTATION_SIGNING_CONTEXT,
+                T::ChainId::get(),
+                genesis,
+                payload,
+                epoch,
+                valid_until,
+            )
+                .encode()
+        }
+
+        /// Votes of a proposal, given its call
+        pub fn get_votes(
+            src_id: ChainId,
+            (nonce, call): (DepositNonce, T::Proposal),
+        ) -> Option<ProposalVotesOf<T>> {
+            Self::proposal_votes(src_id, (nonce, T::Hashing::hash_of(&call)))
+        }
+
+        /// Checks if a chain exists as a whitelisted destination and has not
+        /// been disabled
         pub fn chain_whitelisted(id: ChainId) -> bool {
-            Self::chains(id) != None
+            Self::chains(id).map_or(false, |info| info.enabled)
+        }
+
+        /// Nonce of the last outbound transfer to a whitelisted chain
+        pub fn chain_nonce(id: ChainId) -> Option<DepositNonce> {
+            Self::chains(id).map(|info| info.nonce)
+        }
+
+        /// Checks the recipient of an outbound transfer fits the bound and
+        /// the address length of the destination chain
+        fn ensure_recipient(dest_id: ChainId, to: &[u8]) -> DispatchResult {
+            ensure!(
+                to.len() <= T::MaxRecipientLen::get() as usize,
+                Error::<T>::RecipientTooLong
+            );
+            let recipient_len =
+                Self::chains(dest_id).and_then(|info| info.recipient_len);
+            if let Some(len) = recipient_len {
+                ensure!(to.len() == len as usize, Error::<T>::InvalidRecipient);
+            }
+            Ok(())
+        }
+
+        /// Checks the metadata of an outbound transfer fits the bound
+        fn ensure_metadata_len(metadata: &[u8]) -> DispatchResult {
+            ensure!(
+                metadata.len() <= T::MaxMetadataLen::get() as usize,
+                Error::<T>::MetadataTooLong
+            );
+            Ok(())
+        }
+
+        /// Outbound transfer usage of a resource at the current block, or
+        /// `None` if the resource has no transfer limit
+        pub fn current_usage(
+            r_id: ResourceId,
+        ) -> Option<TransferUsage<T::BlockNumber>> {
+            let limit = Self::transfer_limit(r_id)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut usage = Self::transfer_usage(r_id)
+                .unwrap_or_else(|| TransferUsage::new(now));
+            usage.advance(now, limit.max_per_window, limit.window);
+            Some(usage)
+        }
+
+        /// Checks an outbound transfer against the limits of its resource and
+        /// returns the usage to record once the transfer goes ahead
+        fn checked_usage(
+            r_id: ResourceId,
+            amount: U256,
+        ) -> Result<Option<TransferUsage<T::BlockNumber>>, DispatchError>
+        {
+            let (limit, mut usage) =
+                match (Self::transfer_limit(r_id), Self::current_usage(r_id)) {
+                    (Some(limit), Some(usage)) => (limit, usage),
+                    _ => return Ok(None),
+                };
+            if let Some(max) = limit.max_per_transfer {
+                ensure!(amount <= max, Error::<T>::TransferAmountTooLarge);
+            }
+            if let Some(max) = limit.max_deposits_per_block {
+                ensure!(usage.deposits < max, Error::<T>::TooManyDeposits);
+            }
+            usage.deposits = usage.deposits.saturating_add(1);
+            usage.volume = usage.volume.saturating_add(amount);
+            if let Some(max) = limit.max_per_window {
+                ensure!(
+                    usage.volume <= max,
+                    Error::<T>::TransferVolumeExceeded
+                );
+            }
+            Ok(Some(usage))
+        }
+
+        /// Records the usage returned by [`Self::checked_usage`]
+        fn record_usage(
+            r_id: ResourceId,
+            usage: Option<TransferUsage<T::BlockNumber>>,
+        ) {
+            if let Some(usage) = usage {
+                <TransferUsages<T>>::insert(r_id, usage);
+            }
+        }
+
+        /// Account the fees collected by [`FeePot`] are credited to
+        pub fn fee_pot_account() -> T::AccountId {
+            T::PalletId::get().into_sub_account(b"fees")
+        }
+
+        /// Charges the fee of an outbound transfer to the depositor and hands
+        /// it to the fee handler
+        fn charge_fee(
+            source: &T::AccountId,
+            dest_id: ChainId,
+            resource_id: ResourceId,
+        ) -> DispatchResult {
+            let fee = Self::get_fee(dest_id, resource_id);
+            if fee.is_zero() {
+                return Ok(());
+            }
+            let imbalance = T::Currency::withdraw(
+                source,
+                fee,
+                WithdrawReasons::FEE,
+                ExistenceRequirement::KeepAlive,
+            )
+            .map_err(|_| Error::<T>::InsufficientFee)?;
+            T::FeeHandler::on_unbalanced(imbalance);
+            Ok(())
         }
 
         /// Increments the deposit nonce for the specified chain ID
         fn bump_nonce(id: ChainId) -> DepositNonce {
-            //TODO: use saturating_add here
-            let nonce = Self::chains(id).unwrap_or_default() + 1;
-            <ChainNonces<T>>::insert(id, Some(nonce));
-            nonce
+            <Chains<T>>::mutate(id, |info| match info {
+                Some(info) => {
+                    info.nonce = info.nonce.saturating_add(1);
+                    info.nonce
+                }
+                None => 0,
+            })
         }
 
         // *** Admin methods ****
@@ -426,16 +2005,108 @@ pub mod pallet {
         /// Set a new voting threshold
         pub fn set_relayer_threshold(threshold: u32) -> DispatchResult {
             ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+            Self::ensure_threshold_reachable(threshold, Self::relayer_count())?;
             <RelayerThreshold<T>>::put(threshold);
+            <RelayerThresholdRatio<T>>::kill();
             Self::deposit_event(Event::RelayerThresholdChanged(threshold));
             Ok(())
         }
 
+        /// Set a new voting threshold as a share of the relayer set
+        pub fn set_relayer_threshold_ratio(ratio: Perbill) -> DispatchResult {
+            ensure!(ratio.deconstruct() > 0, Error::<T>::InvalidThreshold);
+            <RelayerThresholdRatio<T>>::put(ratio);
+            Self::deposit_event(Event::RelayerThresholdRatioChanged(ratio));
+            Ok(())
+        }
+
+        /// Number of votes required for a proposal to execute with the
+        /// current relayer set
+        pub fn current_threshold() -> u32 {
+            Self::threshold_for(Self::relayer_count())
+        }
+
+        /// Number of votes required for a proposal to execute with
+        /// `relayers` relayers
+        fn threshold_for(relayers: u32) -> u32 {
+            match Self::relayer_threshold_ratio() {
+                Some(ratio) => ratio.mul_ceil(relayers).max(1),
+                None => Self::relayer_threshold(),
+            }
+        }
+
+        /// Checks `relayers` relayers can meet `threshold`. An empty relayer
+        /// set is not checked, so the threshold can be set before the
+        /// relayers are added.
+        fn ensure_threshold_reachable(
+            threshold: u32,
+            relayers: u32,
+        ) -> DispatchResult {
+            ensure!(
+                relayers == 0 || threshold <= relayers,
+                Error::<T>::ThresholdUnreachable
+            );
+            Ok(())
+        }
+
+        /// Set the fee of outbound transfers of a resource to a chain
+        pub fn set_bridge_fee(
+            dest_id: ChainId,
+            r_id: ResourceId,
+            fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            if fee.is_zero() {
+                <BridgeFees<T>>::remove(dest_id, r_id);
+            } else {
+                <BridgeFees<T>>::insert(dest_id, r_id, fee);
+            }
+            Self::deposit_event(Event::FeeChanged(dest_id, r_id, fee));
+            Ok(())
+        }
+
+        /// Set or remove the outbound transfer limits of a resource
+        pub fn set_resource_transfer_limit(
+            r_id: ResourceId,
+            limit: Option<TransferLimit<T::BlockNumber>>,
+        ) -> DispatchResult {
+            match &limit {
+                Some(limit) => <TransferLimits<T>>::insert(r_id, limit),
+                None => {
+                    <TransferLimits<T>>::remove(r_id);
+                    <TransferUsages<T>>::remove(r_id);
+                }
+            }
+            Self::deposit_event(Event::TransferLimitChanged(r_id, limit));
+            Ok(())
+        }
+
+        /// Set or remove the inbound cap of a resource from a source chain
+        pub fn set_resource_inbound_limit(
+            src_id: ChainId,
+            r_id: ResourceId,
+            limit: Option<InboundLimit<T::BlockNumber>>,
+        ) -> DispatchResult {
+            match &limit {
+                Some(limit) => <InboundLimits<T>>::insert(src_id, r_id, limit),
+                None => {
+                    <InboundLimits<T>>::remove(src_id, r_id);
+                    <InboundUsages<T>>::remove(src_id, r_id);
+                }
+            }
+            Self::deposit_event(Event::InboundLimitChanged(
+                src_id, r_id, limit,
+            ));
+            Ok(())
+        }
+
         /// Register a method for a resource Id, enabling associated transfer
         pub fn register_resource(
             id: ResourceId,
             method: Vec<u8>,
         ) -> DispatchResult {
+            let method: BoundedVec<_, _> = method
+                .try_into()
+                .map_err(|_| Error::<T>::ResourceMethodTooLong)?;
             <Resources<T>>::insert(id, method);
             Ok(())
         }
@@ -450,90 +2121,368 @@ pub mod pallet {
         pub fn whitelist(id: ChainId) -> DispatchResult {
             // Cannot whitelist this chain
             ensure!(id != T::ChainId::get(), Error::<T>::InvalidChainId);
-            // Cannot whitelist with an existing entry
+            // Cannot whitelist with an existing entry, disabled or not, as
+            // that would reset its nonce
             ensure!(
-                !Self::chain_whitelisted(id),
+                !<Chains<T>>::contains_key(id),
                 Error::<T>::ChainAlreadyWhitelisted
             );
-            <ChainNonces<T>>::insert(&id, Some(0));
+            <Chains<T>>::insert(id, ChainInfoOf::<T>::new(0));
             Self::deposit_event(Event::ChainWhitelisted(id));
             Ok(())
         }
 
+        /// Sets the metadata of a chain, whitelisting it if needed
+        pub fn register(
+            id: ChainId,
+            kind: ChainKind,
+            recipient_len: Option<u32>,
+            name: Vec<u8>,
+        ) -> DispatchResult {
+            ensure!(id != T::ChainId::get(), Error::<T>::InvalidChainId);
+            ensure!(
+                recipient_len
+                    .map_or(true, |len| len <= T::MaxRecipientLen::get()),
+                Error::<T>::RecipientTooLong
+            );
+            let name: BoundedVec<_, _> =
+                name.try_into().map_err(|_| Error::<T>::ChainNameTooLong)?;
+            let mut info = match Self::chains(id) {
+                Some(info) => info,
+                None => {
+                    Self::deposit_event(Event::ChainWhitelisted(id));
+                    ChainInfoOf::<T>::new(0)
+                }
+            };
+            info.kind = kind;
+            info.recipient_len = recipient_len;
+            info.name = name;
+            <Chains<T>>::insert(id, info);
+            Self::deposit_event(Event::ChainRegistered(id, kind));
+            Ok(())
+        }
+
+        /// Disables a whitelisted chain, keeping its nonce
+        pub fn disable(id: ChainId) -> DispatchResult {
+            let mut info =
+                Self::chains(id).ok_or(Error::<T>::ChainNotWhitelisted)?;
+            ensure!(info.enabled, Error::<T>::ChainAlreadyDisabled);
+            info.enabled = false;
+            <Chains<T>>::insert(id, info);
+            Self::deposit_event(Event::ChainDisabled(id));
+            Ok(())
+        }
+
+        /// Enables a disabled chain
+        pub fn enable(id: ChainId) -> DispatchResult {
+            let mut info =
+                Self::chains(id).ok_or(Error::<T>::ChainNotWhitelisted)?;
+            ensure!(!info.enabled, Error::<T>::ChainNotDisabled);
+            info.enabled = true;
+            <Chains<T>>::insert(id, info);
+            Self::deposit_event(Event::ChainEnabled(id));
+            Ok(())
+        }
+
         /// Adds a new relayer to the set
         pub fn register_relayer(relayer: T::AccountId) -> DispatchResult {
             ensure!(
                 !Self::is_relayer(&relayer),
                 Error::<T>::RelayerAlreadyExists
             );
+            ensure!(
+                Self::relayer_count() < T::MaxRelayers::get(),
+                Error::<T>::TooManyRelayers
+            );
+            Self::bond_relayer(&relayer)?;
             <Relayers<T>>::insert(&relayer, true);
-            //TODO: use saturating_add
-            <RelayerCount<T>>::mutate(|i| *i += 1);
+            <RelayerSince<T>>::insert(&relayer, Self::bump_relayer_epoch());
+            <RelayerCount<T>>::mutate(|i| *i = i.saturating_add(1));
             Self::deposit_event(Event::RelayerAdded(relayer));
             Ok(())
         }
 
-        /// Removes a relayer from the set
-        pub fn unregister_relayer(relayer: T::AccountId) -> DispatchResult {
-            ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
-            <Relayers<T>>::remove(&relayer);
-            //TODO: use saturating_sub
-            <RelayerCount<T>>::mutate(|i| *i -= 1);
-            Self::deposit_event(Event::RelayerRemoved(relayer));
+        /// Removes a relayer from the set. A threshold the remaining
+        /// relayers could not meet is lowered to their number.
+        pub fn unregister_relayer(relayer: T::AccountId) -> DispatchResult {
+            ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
+            Self::unbond_relayer(&relayer);
+            <Relayers<T>>::remove(&relayer);
+            <RelayerSince<T>>::remove(&relayer);
+            Self::bump_relayer_epoch();
+            let remaining = <RelayerCount<T>>::mutate(|i| {
+                *i = i.saturating_sub(1);
+                *i
+            });
+            Self::deposit_event(Event::RelayerRemoved(relayer));
+            if Self::ensure_threshold_reachable(
+                Self::current_threshold(),
+                remaining,
+            )
+            .is_err()
+            {
+                <RelayerThreshold<T>>::put(remaining);
+                Self::deposit_event(Event::RelayerThresholdChanged(remaining));
+            }
+            Ok(())
+        }
+
+        /// Starts a new epoch of the relayer set and returns it
+        fn bump_relayer_epoch() -> u32 {
+            <RelayerEpoch<T>>::mutate(|epoch| {
+                *epoch = epoch.saturating_add(1);
+                *epoch
+            })
+        }
+
+        /// Reserves the relayer bond from a joining relayer
+        fn bond_relayer(relayer: &T::AccountId) -> DispatchResult {
+            let bond = T::RelayerBond::get();
+            if bond.is_zero() {
+                return Ok(());
+            }
+            T::Currency::reserve(relayer, bond)
+                .map_err(|_| Error::<T>::InsufficientBond)?;
+            <RelayerBonds<T>>::insert(relayer, bond);
+            Ok(())
+        }
+
+        /// Starts the unbonding period of the bond of a leaving relayer
+        fn unbond_relayer(relayer: &T::AccountId) {
+            let bond = <RelayerBonds<T>>::take(relayer);
+            if bond.is_zero() {
+                return;
+            }
+            let unlock_at = <frame_system::Pallet<T>>::block_number()
+                + T::UnbondingPeriod::get();
+            <Unbonding<T>>::mutate(relayer, |unbonding| {
+                let (amount, at) =
+                    unbonding.get_or_insert((Zero::zero(), unlock_at));
+                *amount = amount.saturating_add(bond);
+                *at = unlock_at;
+            });
+        }
+
+        /// Slashes the slash fraction of a relayer's bond, or of its bond
+        /// being unbonded if it has left the relayer set.
+        pub fn slash_relayer(relayer: &T::AccountId) {
+            let fraction = T::SlashFraction::get();
+            let bond = Self::relayer_bond(relayer);
+            if !bond.is_zero() {
+                let slashed = Self::slash_bond(relayer, fraction * bond);
+                <RelayerBonds<T>>::insert(
+                    relayer,
+                    bond.saturating_sub(slashed),
+                );
+            } else if let Some((amount, at)) = Self::unbonding(relayer) {
+                let slashed = Self::slash_bond(relayer, fraction * amount);
+                <Unbonding<T>>::insert(
+                    relayer,
+                    (amount.saturating_sub(slashed), at),
+                );
+            }
+        }
+
+        /// Slashes reserved funds of a relayer and returns the amount slashed
+        fn slash_bond(
+            relayer: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> BalanceOf<T> {
+            if amount.is_zero() {
+                return amount;
+            }
+            let (imbalance, _) = T::Currency::slash_reserved(relayer, amount);
+            let slashed = imbalance.peek();
+            T::Slash::on_unbalanced(imbalance);
+            Self::deposit_event(Event::RelayerSlashed(
+                relayer.clone(),
+                slashed,
+            ));
+            slashed
+        }
+
+        // *** Proposal voting and execution methods ***
+
+        /// Votes of a new proposal made under resource `r_id`, recording the
+        /// relayer set and threshold in force
+        fn new_votes(
+            now: T::BlockNumber,
+            r_id: ResourceId,
+        ) -> ProposalVotesOf<T> {
+            let mut votes = ProposalVotesOf::<T>::default();
+            votes.r_id = r_id;
+            votes.expiry = now + T::ProposalLifetime::get();
+            votes.epoch = Self::relayer_epoch();
+            votes.threshold = Self::current_threshold();
+            votes.relayer_count = Self::relayer_count();
+            votes
+        }
+
+        /// Checks if who is a relayer that joined in or before `epoch`
+        pub fn is_epoch_member(who: &T::AccountId, epoch: u32) -> bool {
+            Self::is_relayer(who) && Self::relayer_since(who) <= epoch
+        }
+
+        /// Commits a vote for a proposal. If the proposal doesn't exist it will be created.
+        ///
+        /// A vote in favour of a proposal conflicting with one the relayer
+        /// already voted for is not counted, but recorded as an equivocation
+        /// before failing with `ConflictingVote`, so the vote pays its fee.
+        fn commit_vote(
+            who: T::AccountId,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            r_id: ResourceId,
+            prop_hash: T::Hash,
+            in_favour: bool,
+        ) -> DispatchResult {
+            let checked = Self::votes_to_commit(
+                &who, nonce, src_id, r_id, prop_hash, in_favour,
+            );
+            let conflicting: DispatchError = Error::<T>::ConflictingVote.into();
+            if checked.as_ref().err() == Some(&conflicting) {
+                Self::record_equivocation(who.clone(), src_id, nonce)?;
+            }
+            let (mut votes, is_new) = checked?;
+
+            if in_favour {
+                votes
+                    .votes_for
+                    .try_push(who.clone())
+                    .map_err(|_| Error::<T>::TooManyVotes)?;
+                <RelayerVotes<T>>::insert((src_id, nonce), &who, prop_hash);
+                Self::deposit_event(Event::VoteFor(src_id, nonce, who.clone()));
+            } else {
+                votes
+                    .votes_against
+                    .try_push(who.clone())
+                    .map_err(|_| Error::<T>::TooManyVotes)?;
+                Self::deposit_event(Event::VoteAgainst(
+                    src_id,
+                    nonce,
+                    who.clone(),
+                ));
+            }
+
+            if is_new {
+                Self::schedule_prune(votes.expiry, src_id, nonce, prop_hash);
+                <InFlightProposals<T>>::insert(src_id, nonce, prop_hash);
+            }
+            <Votes<T>>::insert(src_id, (nonce, prop_hash), votes);
+
             Ok(())
         }
 
-        // *** Proposal voting and execution methods ***
-
-        /// Commits a vote for a proposal. If the proposal doesn't exist it will be created.
-        fn commit_vote(
-            who: T::AccountId,
+        /// Votes of the proposal a vote is committed to, created if the
+        /// proposal does not exist yet, after checking the vote can be
+        /// committed. Also returns whether the proposal is new.
+        fn votes_to_commit(
+            who: &T::AccountId,
             nonce: DepositNonce,
             src_id: ChainId,
-            prop: Box<T::Proposal>,
+            r_id: ResourceId,
+            prop_hash: T::Hash,
             in_favour: bool,
-        ) -> DispatchResult {
+        ) -> Result<(ProposalVotesOf<T>, bool), DispatchError> {
             let now = <frame_system::Pallet<T>>::block_number();
-            let mut votes = match <Votes<T>>::get(src_id, (nonce, prop.clone()))
-            {
+            let existing = <Votes<T>>::get(src_id, (nonce, prop_hash));
+            let is_new = existing.is_none();
+            let mut votes = match existing {
                 Some(v) => v,
-                None => {
-                    let mut v = ProposalVotes::default();
-                    v.expiry = now + T::ProposalLifetime::get();
-                    v
-                }
+                None => Self::new_votes(now, r_id),
             };
+            // Proposals from before the resource was recorded are bound to
+            // the resource of their next vote
+            if votes.r_id == [0; 32] {
+                votes.r_id = r_id;
+            }
+            ensure!(votes.r_id == r_id, Error::<T>::ProposalResourceMismatch);
 
             // Ensure the proposal isn't complete, proposal is not expired and relayer hasn't already votes
             ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
             ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
-            ensure!(!votes.has_voted(&who), Error::<T>::RelayerAlreadyVoted);
+            ensure!(!votes.has_voted(who), Error::<T>::RelayerAlreadyVoted);
+            ensure!(
+                Self::is_epoch_member(who, votes.epoch),
+                Error::<T>::NotEpochMember
+            );
 
+            // A vote for a proposal that expired unresolved no longer binds
+            // the relayer, nor does the proposal hold the nonce
+            let released = |hash: T::Hash| {
+                hash == prop_hash || Self::is_lapsed(src_id, nonce, hash, now)
+            };
             if in_favour {
-                votes.votes_for.push(who.clone());
-                Self::deposit_event(Event::VoteFor(src_id, nonce, who.clone()));
-            } else {
-                votes.votes_against.push(who.clone());
-                Self::deposit_event(Event::VoteAgainst(
-                    src_id,
-                    nonce,
-                    who.clone(),
-                ));
+                let voted = Self::relayer_vote((src_id, nonce), who);
+                ensure!(
+                    voted.map_or(true, released),
+                    Error::<T>::ConflictingVote
+                );
             }
 
-            <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+            // A nonce can only be used by a single proposal
+            if is_new {
+                ensure!(
+                    !Self::nonce_executed(src_id, nonce),
+                    Error::<T>::NonceAlreadyExecuted
+                );
+                ensure!(
+                    Self::in_flight_proposal(src_id, nonce)
+                        .map_or(true, released),
+                    Error::<T>::NonceInUse
+                );
+            }
+            Ok((votes, is_new))
+        }
+
+        /// Checks whether a proposal expired unresolved, so another proposal
+        /// can take its nonce before it is pruned
+        fn is_lapsed(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            prop_hash: T::Hash,
+            now: T::BlockNumber,
+        ) -> bool {
+            Self::proposal_votes(src_id, (nonce, prop_hash)).map_or(
+                true,
+                |votes| {
+                    votes.status == ProposalStatus::Initiated
+                        && votes.is_expired(now)
+                },
+            )
+        }
 
+        /// Records a relayer voting for conflicting proposals and hands it to
+        /// the equivocation handler
+        fn record_equivocation(
+            who: T::AccountId,
+            src_id: ChainId,
+            nonce: DepositNonce,
+        ) -> DispatchResult {
+            ensure!(
+                !<Equivocations<T>>::contains_key(&who, (src_id, nonce)),
+                Error::<T>::AlreadyEquivocated
+            );
+            let now = <frame_system::Pallet<T>>::block_number();
+            <Equivocations<T>>::insert(&who, (src_id, nonce), now);
+            T::OnEquivocation::on_equivocation(&who, src_id, nonce);
+            Self::deposit_event(Event::RelayerEquivocated(who, src_id, nonce));
             Ok(())
         }
 
         /// Attempts to finalize or cancel the proposal if the vote count allows.
+        /// An approved proposal is executed if its call is given, and
+        /// otherwise awaits the call to be supplied.
+        ///
+        /// Returns the weight consumed by executing the proposal, which is zero
+        /// if the proposal was not executed.
         fn try_resolve_proposal(
             nonce: DepositNonce,
             src_id: ChainId,
-            prop: Box<T::Proposal>,
-        ) -> DispatchResult {
-            if let Some(mut votes) =
-                <Votes<T>>::get(src_id, (nonce, prop.clone()))
+            prop_hash: T::Hash,
+            call: Option<Box<T::Proposal>>,
+        ) -> Result<Weight, DispatchError> {
+            if let Some(mut votes) = <Votes<T>>::get(src_id, (nonce, prop_hash))
             {
                 let now = <frame_system::Pallet<T>>::block_number();
                 ensure!(
@@ -542,39 +2491,81 @@ pub mod pallet {
                 );
                 ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
 
-                let status = votes.try_to_complete(
-                    <RelayerThreshold<T>>::get(),
-                    <RelayerCount<T>>::get(),
-                );
-                <Votes<T>>::insert(
-                    src_id,
-                    (nonce, prop.clone()),
-                    votes.clone(),
-                );
+                let epoch = votes.epoch;
+                let status = votes
+                    .try_to_complete(|who| Self::is_epoch_member(who, epoch));
+                if status != ProposalStatus::Initiated {
+                    votes.resolved_at = Some(now);
+                }
+                let r_id = votes.r_id;
+                Self::reward_relayers(&votes);
+                Self::slash_relayers(&votes);
+                <Votes<T>>::insert(src_id, (nonce, prop_hash), votes);
 
                 match status {
                     ProposalStatus::Approved => {
-                        Self::finalize_execution(src_id, nonce, prop)
+                        Self::deposit_event(Event::ProposalApproved(
+                            src_id, nonce,
+                        ));
+                        match call {
+                            Some(call) => Ok(Self::finalize_execution(
+                                src_id, nonce, r_id, call,
+                            )),
+                            None => {
+                                <AwaitingPreimage<T>>::insert(
+                                    src_id, nonce, prop_hash,
+                                );
+                                Ok(0)
+                            }
+                        }
                     }
                     ProposalStatus::Rejected => {
-                        Self::cancel_execution(src_id, nonce)
+                        Self::cancel_execution(src_id, nonce, prop_hash)?;
+                        Ok(0)
                     }
-                    _ => Ok(()),
+                    _ => Ok(0),
                 }
             } else {
                 Err(Error::<T>::ProposalDoesNotExist)?
             }
         }
 
+        /// Checks a relayer may vote in favour of the proposed call, then
+        /// commits the vote. Returns the weight consumed by executing the
+        /// proposal, which is zero if the proposal was not executed.
+        fn acknowledge(
+            who: T::AccountId,
+            nonce: DepositNonce,
+            src_id: ChainId,
+            r_id: ResourceId,
+            call: Box<T::Proposal>,
+        ) -> Result<Weight, DispatchError> {
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(
+                Self::chain_whitelisted(src_id),
+                Error::<T>::ChainNotWhitelisted
+            );
+            Self::ensure_not_paused(src_id)?;
+            Self::ensure_call_matches_resource(r_id, &call)?;
+            ensure!(
+                T::ProposalFilter::contains(&call),
+                Error::<T>::ProposalFiltered
+            );
+            let prop_hash = T::Hashing::hash_of(&call);
+            Self::vote_for(who, nonce, src_id, r_id, prop_hash, Some(call))
+        }
+
         /// Commits a vote in favour of the proposal and executes it if the vote threshold is met.
         fn vote_for(
             who: T::AccountId,
             nonce: DepositNonce,
             src_id: ChainId,
-            prop: Box<T::Proposal>,
-        ) -> DispatchResult {
-            Self::commit_vote(who, nonce, src_id, prop.clone(), true)?;
-            Self::try_resolve_proposal(nonce, src_id, prop)
+            r_id: ResourceId,
+            prop_hash: T::Hash,
+            call: Option<Box<T::Proposal>>,
+        ) -> Result<Weight, DispatchError> {
+            Self::commit_vote(who, nonce, src_id, r_id, prop_hash, true)?;
+            Self::try_resolve_proposal(nonce, src_id, prop_hash, call)
         }
 
         /// Commits a vote against the proposal and cancels it if more than
@@ -583,40 +2574,450 @@ pub mod pallet {
             who: T::AccountId,
             nonce: DepositNonce,
             src_id: ChainId,
-            prop: Box<T::Proposal>,
-        ) -> DispatchResult {
-            Self::commit_vote(who, nonce, src_id, prop.clone(), false)?;
-            Self::try_resolve_proposal(nonce, src_id, prop)
+            r_id: ResourceId,
+            prop_hash: T::Hash,
+            call: Option<Box<T::Proposal>>,
+        ) -> Result<Weight, DispatchError> {
+            Self::commit_vote(who, nonce, src_id, r_id, prop_hash, false)?;
+            Self::try_resolve_proposal(nonce, src_id, prop_hash, call)
+        }
+
+        /// Executes a proposal if the valid attestations of distinct relayers
+        /// meet the relayer threshold. The attesting relayers are recorded as
+        /// its votes in favour. Returns the actual weight consumed by the
+        /// proposed call.
+        fn execute_attested(
+            nonce: DepositNonce,
+            src_id: ChainId,
+            r_id: ResourceId,
+            call: Box<T::Proposal>,
+            valid_until: T::BlockNumber,
+            attestations: AttestationsOf<T>,
+        ) -> Result<Weight, DispatchError> {
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                now <= valid_until
+                    && valid_until <= now + T::ProposalLifetime::get(),
+                Error::<T>::InvalidAttestationPeriod
+            );
+            ensure!(
+                !Self::nonce_executed(src_id, nonce),
+                Error::<T>::NonceAlreadyExecuted
+            );
+            ensure!(
+                Self::in_flight_proposal(src_id, nonce).map_or(true, |hash| {
+                    Self::is_lapsed(src_id, nonce, hash, now)
+                }),
+                Error::<T>::NonceInUse
+            );
+
+            let prop_hash = T::Hashing::hash_of(&call);
+            let mut votes = Self::new_votes(now, r_id);
+            for (relayer, signature) in attestations {
+                if !Self::is_relayer(&relayer) || votes.has_voted(&relayer) {
+                    continue;
+                }
+                let payload = VotePayload {
+                    relayer,
+                    src_id,
+                    nonce,
+                    r_id,
+                    prop_hash,
+                    in_favour: true,
+                };
+                let message = Self::attestation_message(
+                    &payload,
+                    votes.epoch,
+                    valid_until,
+                );
+                if !signature.verify(&message[..], &payload.relayer) {
+                    continue;
+                }
+                votes
+                    .votes_for
+                    .try_push(payload.relayer)
+                    .map_err(|_| Error::<T>::TooManyVotes)?;
+            }
+            // The attesting relayers are all members of the current epoch
+            let status = votes.try_to_complete(|_| true);
+            ensure!(
+                status == ProposalStatus::Approved,
+                Error::<T>::InsufficientAttestations
+            );
+            votes.resolved_at = Some(now);
+
+            Self::reward_relayers(&votes);
+            Self::schedule_prune(votes.expiry, src_id, nonce, prop_hash);
+            <InFlightProposals<T>>::insert(src_id, nonce, prop_hash);
+            <Votes<T>>::insert(src_id, (nonce, prop_hash), votes);
+            Self::deposit_event(Event::ProposalApproved(src_id, nonce));
+            Ok(Self::finalize_execution(src_id, nonce, r_id, call))
+        }
+
+        /// Credits the relayer reward to every relayer of the proposal's
+        /// epoch that voted for the outcome of a resolved proposal. Does
+        /// nothing if the proposal is not resolved.
+        fn reward_relayers(votes: &ProposalVotesOf<T>) {
+            let reward = T::RelayerReward::get();
+            if reward.is_zero() {
+                return;
+            }
+            let winners = match votes.status {
+                ProposalStatus::Approved => &votes.votes_for,
+                ProposalStatus::Rejected => &votes.votes_against,
+                _ => return,
+            };
+            let members = winners
+                .iter()
+                .filter(|who| Self::is_epoch_member(who, votes.epoch));
+            for relayer in members {
+                <PendingRewards<T>>::mutate(relayer, |pending| {
+                    *pending = pending.saturating_add(reward)
+                });
+            }
+        }
+
+        /// Slashes every relayer of the proposal's epoch that voted against
+        /// the outcome of a resolved proposal. Does nothing if the proposal
+        /// is not resolved.
+        fn slash_relayers(votes: &ProposalVotesOf<T>) {
+            let losers = match votes.status {
+                ProposalStatus::Approved => &votes.votes_against,
+                ProposalStatus::Rejected => &votes.votes_for,
+                _ => return,
+            };
+            let members = losers
+                .iter()
+                .filter(|who| Self::is_epoch_member(who, votes.epoch));
+            for relayer in members {
+                Self::slash_relayer(relayer);
+            }
         }
 
         /// Execute the proposal and signals the result as an event
+        ///
+        /// A failing call is recorded in the proposal status rather than
+        /// returned, so the vote that approved the proposal is kept. A call
+        /// exceeding the inbound cap of resource `r_id` is parked until it is
+        /// retried or approved, or expires. Returns the actual weight consumed
+        /// by the proposed call.
         fn finalize_execution(
             src_id: ChainId,
             nonce: DepositNonce,
+            r_id: ResourceId,
             call: Box<T::Proposal>,
-        ) -> DispatchResult {
-            Self::deposit_event(Event::ProposalApproved(src_id, nonce));
-            call.dispatch(
-                frame_system::RawOrigin::Signed(Self::account_id()).into(),
-            )
-            .map(|_| ())
-            .map_err(|e| e.error)?;
-            Self::deposit_event(Event::ProposalSucceeded(src_id, nonce));
-            Ok(())
+        ) -> Weight {
+            let prop_hash = T::Hashing::hash_of(&call);
+            // Executed before the upgrade to call hashes, the proposal fails
+            // without dispatching its call again
+            let (weight, result) =
+                if Self::executed_before_upgrade(src_id, nonce, &call) {
+                    (0, Err(Error::<T>::NonceAlreadyExecuted.into()))
+                } else {
+                    Self::dispatch_proposal(src_id, r_id, *call, true)
+                };
+            if Self::exceeds_inbound_cap(&result) {
+                let now = <frame_system::Pallet<T>>::block_number();
+                let expiry = now + T::PendingLifetime::get();
+                <PendingProposals<T>>::insert(
+                    src_id,
+                    (nonce, prop_hash),
+                    (r_id, expiry),
+                );
+                Self::deposit_event(Event::ProposalParked(src_id, nonce));
+            } else {
+                Self::record_execution(src_id, nonce, prop_hash, result);
+            }
+            weight
+        }
+
+        /// Checks whether a proposal was executed before votes were keyed by
+        /// the hash of the call
+        fn executed_before_upgrade(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            call: &T::Proposal,
+        ) -> bool {
+            let key = Blake2_256::hash(&(nonce, call).encode());
+            Self::legacy_executed(src_id, key)
+        }
+
+        /// Executes a proposal approved by votes on its hash, after checking
+        /// the call against the hash and the resource of the votes. Returns
+        /// the actual weight consumed by the proposed call.
+        fn execute_preimage(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            call: Box<T::Proposal>,
+        ) -> Result<Weight, DispatchError> {
+            let prop_hash = Self::awaiting_preimage(src_id, nonce)
+                .ok_or(Error::<T>::ProposalNotAwaitingPreimage)?;
+            ensure!(
+                T::Hashing::hash_of(&call) == prop_hash,
+                Error::<T>::ProposalHashMismatch
+            );
+            let r_id = Self::proposal_votes(src_id, (nonce, prop_hash))
+                .ok_or(Error::<T>::ProposalDoesNotExist)?
+                .r_id;
+            Self::ensure_call_matches_resource(r_id, &call)?;
+            <AwaitingPreimage<T>>::remove(src_id, nonce);
+            Ok(Self::finalize_execution(src_id, nonce, r_id, call))
+        }
+
+        /// Executes a parked proposal. The proposal stays parked if it still
+        /// exceeds an inbound cap. Returns the actual weight consumed by the
+        /// proposed call.
+        fn execute_pending(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            call: Box<T::Proposal>,
+            enforce_caps: bool,
+        ) -> Result<Weight, DispatchError> {
+            let prop_hash = T::Hashing::hash_of(&call);
+            let (r_id, expiry) =
+                Self::pending_proposal(src_id, (nonce, prop_hash))
+                    .ok_or(Error::<T>::ProposalNotPending)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(expiry > now, Error::<T>::ProposalExpired);
+            let (weight, result) =
+                Self::dispatch_proposal(src_id, r_id, *call, enforce_caps);
+            ensure!(
+                !Self::exceeds_inbound_cap(&result),
+                Error::<T>::InboundCapExceeded
+            );
+            <PendingProposals<T>>::remove(src_id, (nonce, prop_hash));
+            Self::record_execution(src_id, nonce, prop_hash, result);
+            Ok(weight)
+        }
+
+        /// Dispatches a proposal from the bridge account, discarding its
+        /// storage changes if it fails. A call the proposal filter does not
+        /// allow fails without being dispatched. Returns the actual weight
+        /// consumed along with the result.
+        fn dispatch_proposal(
+            src_id: ChainId,
+            r_id: ResourceId,
+            call: T::Proposal,
+            enforce_caps: bool,
+        ) -> (Weight, DispatchResultWithPostInfo) {
+            if !T::ProposalFilter::contains(&call) {
+                return (0, Err(Error::<T>::ProposalFiltered.into()));
+            }
+            let dispatch_info = call.get_dispatch_info();
+            <InboundContext<T>>::put((src_id, r_id, enforce_caps));
+            let result = with_transaction(|| {
+                let result = call.dispatch(
+                    frame_system::RawOrigin::Signed(Self::account_id()).into(),
+                );
+                if result.is_ok() {
+                    TransactionOutcome::Commit(result)
+                } else {
+                    TransactionOutcome::Rollback(result)
+                }
+            });
+            <InboundContext<T>>::kill();
+            (extract_actual_weight(&result, &dispatch_info), result)
+        }
+
+        /// Checks whether a proposal failed for exceeding an inbound cap
+        fn exceeds_inbound_cap(result: &DispatchResultWithPostInfo) -> bool {
+            let cap_exceeded: DispatchError =
+                Error::<T>::InboundCapExceeded.into();
+            matches!(result, Err(e) if e.error == cap_exceeded)
+        }
+
+        /// Signals the result of executing a proposal as an event, and
+        /// records a failure in the proposal status
+        fn record_execution(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            prop_hash: T::Hash,
+            result: DispatchResultWithPostInfo,
+        ) {
+            <InFlightProposals<T>>::remove(src_id, nonce);
+            <ExecutedNonces<T>>::insert(src_id, nonce, true);
+            match result {
+                Ok(_) => {
+                    Self::deposit_event(Event::ProposalSucceeded(src_id, nonce))
+                }
+                Err(e) => {
+                    log::warn!(
+                        "proposal {} from chain {} failed: {:?}",
+                        nonce,
+                        src_id,
+                        e.error
+                    );
+                    <Votes<T>>::mutate(src_id, (nonce, prop_hash), |votes| {
+                        if let Some(votes) = votes {
+                            votes.status =
+                                ProposalStatus::ExecutionFailed(e.error);
+                        }
+                    });
+                    Self::deposit_event(Event::ProposalFailed(src_id, nonce));
+                }
+            }
+        }
+
+        /// Queues a proposal to be checked for pruning at block `at`, or at
+        /// the first following block whose queue is not full.
+        pub(crate) fn schedule_prune(
+            at: T::BlockNumber,
+            src_id: ChainId,
+            nonce: DepositNonce,
+            prop_hash: T::Hash,
+        ) {
+            // The queue of the current block may already have been processed
+            let next = <frame_system::Pallet<T>>::block_number() + One::one();
+            let mut at = at.max(next);
+            // Nothing was queued before, so start processing from this block
+            if <NextPruneBlock<T>>::get().is_none() {
+                <NextPruneBlock<T>>::put(at);
+            }
+            while <PruneQueue<T>>::mutate(at, |queue| {
+                queue.try_push((src_id, nonce, prop_hash)).is_err()
+            }) {
+                at += One::one();
+            }
+        }
+
+        /// Processes the prune queue up to block `now`, using at most
+        /// `max_weight`. Returns the weight consumed.
+        pub(crate) fn prune_proposals(
+            now: T::BlockNumber,
+            max_weight: Weight,
+        ) -> Weight {
+            // Reading and updating the cursor
+            let mut consumed = T::WeightInfo::on_idle(0);
+            if consumed > max_weight {
+                return 0;
+            }
+            // Reading and updating the queue of a block
+            let queue_weight = T::DbWeight::get().reads_writes(1, 1);
+            // Reading a proposal and either removing or rescheduling it
+            let entry_weight =
+                T::WeightInfo::on_idle(1).saturating_sub(consumed);
+
+            // Nothing has been queued yet
+            let mut block = match <NextPruneBlock<T>>::get() {
+                Some(block) => block,
+                None => return consumed,
+            };
+            while block <= now {
+                if consumed.saturating_add(queue_weight) > max_weight {
+                    break;
+                }
+                consumed = consumed.saturating_add(queue_weight);
+
+                let mut queue = <PruneQueue<T>>::take(block).into_inner();
+                while let Some((src_id, nonce, prop_hash)) = queue.pop() {
+                    if consumed.saturating_add(entry_weight) > max_weight {
+                        queue.push((src_id, nonce, prop_hash));
+                        break;
+                    }
+                    consumed = consumed.saturating_add(entry_weight);
+                    Self::prune_proposal(now, src_id, nonce, prop_hash);
+                }
+                if !queue.is_empty() {
+                    let queue: BoundedVec<_, _> = queue
+                        .try_into()
+                        .expect("shorter than the queue taken; qed");
+                    <PruneQueue<T>>::insert(block, queue);
+                    break;
+                }
+                block += One::one();
+            }
+            <NextPruneBlock<T>>::put(block);
+            consumed
+        }
+
+        /// Removes a proposal if it has expired unresolved or parked by an
+        /// inbound cap, or if it is complete and its retention period since it
+        /// was resolved is over. Otherwise it is queued again for when it can
+        /// be removed.
+        fn prune_proposal(
+            now: T::BlockNumber,
+            src_id: ChainId,
+            nonce: DepositNonce,
+            prop_hash: T::Hash,
+        ) {
+            let key = (nonce, prop_hash);
+            // A parked proposal is kept until it is released or expires
+            if let Some((_, expiry)) = Self::pending_proposal(src_id, &key) {
+                if expiry > now {
+                    Self::schedule_prune(expiry, src_id, nonce, prop_hash);
+                    return;
+                }
+                <PendingProposals<T>>::remove(src_id, &key);
+                <Votes<T>>::remove(src_id, &key);
+                Self::release_nonce(src_id, nonce, prop_hash);
+                Self::deposit_event(Event::ProposalExpired(src_id, nonce));
+                return;
+            }
+            let votes = match <Votes<T>>::get(src_id, &key) {
+                Some(votes) => votes,
+                None => return,
+            };
+            let prune_at = match votes.resolved_at {
+                Some(at) if votes.is_complete() => {
+                    at + T::ProposalRetention::get()
+                }
+                _ => votes.expiry,
+            };
+            if prune_at > now {
+                Self::schedule_prune(prune_at, src_id, nonce, prop_hash);
+                return;
+            }
+            <Votes<T>>::remove(src_id, &key);
+            match votes.status {
+                ProposalStatus::Initiated => {
+                    Self::release_nonce(src_id, nonce, prop_hash);
+                    Self::deposit_event(Event::ProposalExpired(src_id, nonce));
+                }
+                // The nonce was released when the proposal was rejected
+                ProposalStatus::Rejected => {}
+                // Approved by hash, but the call was never supplied
+                _ if Self::awaiting_preimage(src_id, nonce)
+                    == Some(prop_hash) =>
+                {
+                    <AwaitingPreimage<T>>::remove(src_id, nonce);
+                    Self::release_nonce(src_id, nonce, prop_hash);
+                    Self::deposit_event(Event::ProposalExpired(src_id, nonce));
+                }
+                _ => {
+                    <RelayerVotes<T>>::remove_prefix((src_id, nonce), None);
+                    <RejectedVotes<T>>::remove_prefix((src_id, nonce), None);
+                }
+            }
         }
 
-        /// Cancels a proposal.
+        /// Cancels a proposal, so its nonce can be used by another proposal.
         fn cancel_execution(
             src_id: ChainId,
             nonce: DepositNonce,
+            prop_hash: T::Hash,
         ) -> DispatchResult {
+            Self::release_nonce(src_id, nonce, prop_hash);
             Self::deposit_event(Event::ProposalRejected(src_id, nonce));
             Ok(())
         }
 
+        /// Frees an inbound nonce held by the given proposal
+        fn release_nonce(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            prop_hash: T::Hash,
+        ) {
+            if Self::in_flight_proposal(src_id, nonce) == Some(prop_hash) {
+                <InFlightProposals<T>>::remove(src_id, nonce);
+                <RelayerVotes<T>>::remove_prefix((src_id, nonce), None);
+                <RejectedVotes<T>>::remove_prefix((src_id, nonce), None);
+            }
+        }
+
         /// Initiates a transfer of a fungible asset out of the chain. This should be called by
-        /// another pallet
+        /// another pallet. The bridge fee is charged to `source`.
         pub fn transfer_fungible(
+            source: T::AccountId,
             dest_id: ChainId,
             resource_id: ResourceId,
             to: Vec<u8>,
@@ -626,6 +3027,11 @@ pub mod pallet {
                 Self::chain_whitelisted(dest_id),
                 Error::<T>::ChainNotWhitelisted
             );
+            Self::ensure_not_paused(dest_id)?;
+            Self::ensure_recipient(dest_id, &to)?;
+            let usage = Self::checked_usage(resource_id, amount)?;
+            Self::charge_fee(&source, dest_id, resource_id)?;
+            Self::record_usage(resource_id, usage);
             let nonce = Self::bump_nonce(dest_id);
             Self::deposit_event(Event::FungibleTransfer(
                 dest_id,
@@ -638,8 +3044,9 @@ pub mod pallet {
         }
 
         /// Initiates a transfer of a nunfungible asset out of the chain. This should be called by
-        /// another pallet
+        /// another pallet. The bridge fee is charged to `source`.
         pub fn transfer_nonfungible(
+            source: T::AccountId,
             dest_id: ChainId,
             resource_id: ResourceId,
             token_id: Vec<u8>,
@@ -650,6 +3057,12 @@ pub mod pallet {
                 Self::chain_whitelisted(dest_id),
                 Error::<T>::ChainNotWhitelisted
             );
+            Self::ensure_not_paused(dest_id)?;
+            Self::ensure_recipient(dest_id, &to)?;
+            Self::ensure_metadata_len(&metadata)?;
+            let usage = Self::checked_usage(resource_id, U256::one())?;
+            Self::charge_fee(&source, dest_id, resource_id)?;
+            Self::record_usage(resource_id, usage);
             let nonce = Self::bump_nonce(dest_id);
             Self::deposit_event(Event::NonFungibleTransfer(
                 dest_id,
@@ -663,8 +3076,9 @@ pub mod pallet {
         }
 
         /// Initiates a transfer of generic data out of the chain. This should be called by
-        /// another pallet.
+        /// another pallet. The bridge fee is charged to `source`.
         pub fn transfer_generic(
+            source: T::AccountId,
             dest_id: ChainId,
             resource_id: ResourceId,
             metadata: Vec<u8>,
@@ -673,6 +3087,11 @@ pub mod pallet {
                 Self::chain_whitelisted(dest_id),
                 Error::<T>::ChainNotWhitelisted
             );
+            Self::ensure_not_paused(dest_id)?;
+            Self::ensure_metadata_len(&metadata)?;
+            let usage = Self::checked_usage(resource_id, U256::zero())?;
+            Self::charge_fee(&source, dest_id, resource_id)?;
+            Self::record_usage(resource_id, usage);
             let nonce = Self::bump_nonce(dest_id);
             Self::deposit_event(Event::GenericTransfer(
                 dest_id,
@@ -697,6 +3116,269 @@ pub fn derive_resource_id(chain: u8, id: &[u8]) -> ResourceId {
     r_id
 }
 
+/// Lets the handlers of inbound proposals report the value they release, so
+/// it counts towards the inbound cap of the resource the proposal was made
+/// under.
+pub trait InboundVolume {
+    /// Notes `amount` released by the proposal being executed. Fails with
+    /// `InboundCapExceeded` if the cap does not allow it, which parks the
+    /// proposal. Outside of a proposal this does nothing.
+    fn note_inbound(amount: U256) -> DispatchResult;
+}
+
+impl<T: Config> InboundVolume for Pallet<T> {
+    fn note_inbound(amount: U256) -> DispatchResult {
+        let (src_id, resource_id, enforce_caps) =
+            match <InboundContext<T>>::get() {
+                Some(context) => context,
+                None => return Ok(()),
+            };
+        let limit = match <Pallet<T>>::inbound_limit(src_id, resource_id) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        let now = <frame_system::Pallet<T>>::block_number();
+        let mut usage = <Pallet<T>>::inbound_usage(src_id, resource_id)
+            .unwrap_or_else(|| types::TransferUsage::new(now));
+        usage.advance(now, Some(limit.max_per_window), limit.window);
+        usage.volume = usage.volume.saturating_add(amount);
+        usage.deposits = usage.deposits.saturating_add(1);
+        if enforce_caps && usage.volume > limit.max_per_window {
+            return Err(Error::<T>::InboundCapExceeded.into());
+        }
+        <InboundUsages<T>>::insert(src_id, resource_id, usage);
+        Ok(())
+    }
+}
+
+/// Checks whether a proposed call is the method a resource ID maps to.
+pub trait ResourceCallMatcher<Call> {
+    fn matches(method: &[u8], call: &Call) -> bool;
+}
+
+/// Accepts any call for any resource.
+impl<Call> ResourceCallMatcher<Call> for () {
+    fn matches(_: &[u8], _: &Call) -> bool {
+        true
+    }
+}
+
+/// Matches resource methods of the form `Pallet.call` against the pallet and
+/// function names of the proposed call, as given by the runtime.
+pub struct MatchCallName;
+impl<Call: GetCallMetadata> ResourceCallMatcher<Call> for MatchCallName {
+    fn matches(method: &[u8], call: &Call) -> bool {
+        let metadata = call.get_call_metadata();
+        let pallet = metadata.pallet_name.as_bytes();
+        let function = metadata.function_name.as_bytes();
+        method.len() == pallet.len() + 1 + function.len()
+            && method.starts_with(pallet)
+            && method[pallet.len()] == b'.'
+            && method.ends_with(function)
+    }
+}
+
+/// Handles a relayer that voted for conflicting proposals under the same
+/// source chain and nonce.
+pub trait OnEquivocation<AccountId> {
+    fn on_equivocation(
+        relayer: &AccountId,
+        src_id: ChainId,
+        nonce: DepositNonce,
+    );
+}
+
+impl<AccountId> OnEquivocation<AccountId> for () {
+    fn on_equivocation(_: &AccountId, _: ChainId, _: DepositNonce) {}
+}
+
+impl<AccountId, A, B> OnEquivocation<AccountId> for (A, B)
+where
+    A: OnEquivocation<AccountId>,
+    B: OnEquivocation<AccountId>,
+{
+    fn on_equivocation(
+        relayer: &AccountId,
+        src_id: ChainId,
+        nonce: DepositNonce,
+    ) {
+        A::on_equivocation(relayer, src_id, nonce);
+        B::on_equivocation(relayer, src_id, nonce);
+    }
+}
+
+/// Slashes the bond of an equivocating relayer by the slash fraction.
+pub struct SlashEquivocator<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnEquivocation<T::AccountId> for SlashEquivocator<T> {
+    fn on_equivocation(relayer: &T::AccountId, _: ChainId, _: DepositNonce) {
+        <Pallet<T>>::slash_relayer(relayer);
+    }
+}
+
+/// Suspends an equivocating relayer by removing it from the relayer set. Its
+/// bond starts unbonding. The relayer is removed even if the remaining
+/// relayers cannot meet the vote threshold, which is then lowered to their
+/// number.
+pub struct RemoveEquivocator<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnEquivocation<T::AccountId> for RemoveEquivocator<T> {
+    fn on_equivocation(relayer: &T::AccountId, _: ChainId, _: DepositNonce) {
+        // Fails only if the relayer has already been removed
+        let _ = <Pallet<T>>::unregister_relayer(relayer.clone());
+    }
+}
+
+/// Credits the fees charged on outbound transfers to the fee pot account of
+/// the bridge.
+pub struct FeePot<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for FeePot<T> {
+    fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+        T::Currency::resolve_creating(&<Pallet<T>>::fee_pot_account(), amount);
+    }
+}
+
+/// Creates relayer accounts and signs attestations for them, and creates
+/// the proposals voted on, in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, Signature, Proposal> {
+    /// Creates a new account whose key can sign relayer votes
+    fn create_signer(index: u32) -> AccountId;
+
+    /// Signs `message` with the key of `signer`
+    fn sign(signer: &AccountId, message: &[u8]) -> Signature;
+
+    /// Creates a proposal the proposal filter allows
+    fn make_proposal() -> Proposal;
+
+    /// Method of the resource the proposals of `make_proposal` are made under
+    fn proposal_method() -> Vec<u8>;
+}
+
+/// Custom validity errors of relayer votes in the transaction pool
+#[repr(u8)]
+pub enum ValidityError {
+    /// Vote is not from a relayer
+    NotRelayer = 0,
+    /// Vote would fail to be committed
+    CannotCommit = 1,
+}
+
+impl From<ValidityError> for u8 {
+    fn from(err: ValidityError) -> Self {
+        err as u8
+    }
+}
+
+/// Keeps votes of accounts that are not relayers, and votes a relayer has
+/// already cast, out of the transaction pool. Committed votes pay no fee, so
+/// they would otherwise be free to spam.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckRelayerVote<T: Config + Send + Sync>(
+    sp_std::marker::PhantomData<T>,
+);
+
+impl<T: Config + Send + Sync> CheckRelayerVote<T> {
+    pub fn new() -> Self {
+        Self(sp_std::marker::PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync> Default for CheckRelayerVote<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckRelayerVote<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckRelayerVote")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckRelayerVote<T>
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "CheckRelayerVote";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        // The proposals voted on, as (src_id, nonce, call hash)
+        let votes: Vec<(ChainId, DepositNonce, T::Hash)> = match call
+            .is_sub_type()
+        {
+            Some(Call::acknowledge_proposal {
+                nonce,
+                src_id,
+                call,
+                ..
+            }) => vec![(*src_id, *nonce, T::Hashing::hash_of(call))],
+            Some(Call::reject_proposal {
+                nonce,
+                src_id,
+                call,
+                ..
+            }) => vec![(*src_id, *nonce, T::Hashing::hash_of(call))],
+            Some(Call::acknowledge_proposal_hash {
+                nonce,
+                src_id,
+                prop_hash,
+                ..
+            }) => vec![(*src_id, *nonce, *prop_hash)],
+            Some(Call::reject_proposal_hash {
+                nonce,
+                src_id,
+                prop_hash,
+                ..
+            }) => vec![(*src_id, *nonce, *prop_hash)],
+            Some(Call::acknowledge_proposals_batch { proposals }) => proposals
+                .iter()
+                .map(|(nonce, src_id, _, call)| {
+                    (*src_id, *nonce, T::Hashing::hash_of(call))
+                })
+                .collect(),
+            _ => return Ok(ValidTransaction::default()),
+        };
+
+        if !<Pallet<T>>::is_relayer(who) {
+            return Err(InvalidTransaction::Custom(
+                ValidityError::NotRelayer.into(),
+            )
+            .into());
+        }
+        let mut provides = Vec::with_capacity(votes.len());
+        for (src_id, nonce, prop_hash) in votes {
+            if <Pallet<T>>::is_stale_vote(who, src_id, nonce, prop_hash) {
+                return Err(InvalidTransaction::Stale.into());
+            }
+            provides.push((who, src_id, nonce, prop_hash).encode());
+        }
+        Ok(ValidTransaction {
+            provides,
+            ..Default::default()
+        })
+    }
+}
+
 /// Simple ensure origin for the bridge account
 pub struct EnsureBridge<T>(sp_std::marker::PhantomData<T>);
 impl<T: Config> EnsureOrigin<T::Origin> for EnsureBridge<T> {
@@ -716,6 +3398,6 @@ impl<T: Config> EnsureOrigin<T::Origin> for EnsureBridge<T> {
     /// ** Should be used for benchmarking only!!! **
     #[cfg(feature = "runtime-benchmarks")]
     fn successful_origin() -> T::Origin {
-        T::Origin::from(frame_system::Origin::Signed(<Module<T>>::account_id()))
+        T::Origin::from(frame_system::Origin::Signed(<Pallet<T>>::account_id()))
     }
 }
@@ -0,0 +1,108 @@
+//! Storage migrations for chainbridge
+
+use crate::pallet::LegacyExecutions;
+use crate::types::{DepositNonce, ProposalStatus};
+use crate::{ChainId, ChainInfoOf, Chains, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::migration::{storage_iter, take_storage_item},
+    traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+    weights::Weight,
+    Blake2_256, BoundedVec, StorageHasher,
+};
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Runs the migrations the on-chain storage version has not been through
+/// yet.
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    if Pallet::<T>::on_chain_storage_version() < 1 {
+        weight = weight.saturating_add(v1::migrate::<T>());
+        StorageVersion::new(1).put::<Pallet<T>>();
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    if Pallet::<T>::on_chain_storage_version() < 2 {
+        weight = weight.saturating_add(v2::migrate::<T>());
+        StorageVersion::new(2).put::<Pallet<T>>();
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+    weight
+}
+
+/// Moves the nonces of `ChainNonces` into the `Chains` metadata records.
+pub mod v1 {
+    use super::*;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+        let mut migrated: Weight = 0;
+        // The map cannot be iterated, as its hasher does not keep the key,
+        // but chain IDs are a single byte so every key is visited.
+        for id in ChainId::MIN..=ChainId::MAX {
+            let nonce =
+                take_storage_item::<_, Option<DepositNonce>, Blake2_256>(
+                    pallet,
+                    b"ChainNonces",
+                    id,
+                )
+                .flatten();
+            if let Some(nonce) = nonce {
+                <Chains<T>>::insert(id, ChainInfoOf::<T>::new(nonce));
+                migrated += 1;
+            }
+        }
+        let keys = ChainId::MAX as Weight + 1;
+        T::DbWeight::get().reads_writes(keys, keys + migrated)
+    }
+}
+
+/// Takes every proposal out of `Votes`, which was keyed by the proposed call
+/// itself. Neither the nonce nor the call of a proposal can be recovered
+/// from its key, so executed proposals are recorded in `LegacyExecutions`
+/// by the hash of their nonce and call, and open proposals are voted on
+/// again.
+pub mod v2 {
+    use super::*;
+
+    /// Votes of a proposal before storage version 2
+    #[derive(Encode, Decode)]
+    pub struct OldProposalVotes<AccountId, BlockNumber, MaxVotes: Get<u32>> {
+        pub votes_for: BoundedVec<AccountId, MaxVotes>,
+        pub votes_against: BoundedVec<AccountId, MaxVotes>,
+        pub status: ProposalStatus,
+        pub expiry: BlockNumber,
+    }
+
+    pub type OldProposalVotesOf<T> = OldProposalVotes<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        <T as Config>::MaxRelayers,
+    >;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+        // The source chain is found from the hash of the first key, as chain
+        // IDs are a single byte
+        let chains: BTreeMap<[u8; 32], ChainId> = (ChainId::MIN..=ChainId::MAX)
+            .map(|id| (Blake2_256::hash(&id.encode()), id))
+            .collect();
+        let mut drained: Weight = 0;
+        let mut recorded: Weight = 0;
+        for (key, votes) in
+            storage_iter::<OldProposalVotesOf<T>>(pallet, b"Votes").drain()
+        {
+            drained += 1;
+            if votes.status != ProposalStatus::Approved || key.len() != 64 {
+                continue;
+            }
+            let (chain, proposal) = key.split_at(32);
+            let src_id = chains.get(chain);
+            let proposal: Result<[u8; 32], _> = proposal.try_into();
+            if let (Some(src_id), Ok(proposal)) = (src_id, proposal) {
+                <LegacyExecutions<T>>::insert(src_id, proposal, true);
+                recorded += 1;
+            }
+        }
+        T::DbWeight::get().reads_writes(drained, drained + recorded)
+    }
+}
@@ -1,18 +1,22 @@
 #![deny(warnings)]
 use crate as pallet_chainbridge;
 use crate::ResourceId;
-use frame_support::traits::StorageMapShim;
+use frame_support::traits::{Contains, GenesisBuild, Get, StorageMapShim};
 use frame_support::{
-    assert_ok, parameter_types, traits::SortedMembers, PalletId,
+    assert_ok, parameter_types, traits::SortedMembers,
+    weights::constants::RocksDbWeight, PalletId,
 };
 use frame_system as system;
 use frame_system::EnsureSignedBy;
 use pallet_chainbridge::types::ChainId;
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
+    transaction_validity::{TransactionLongevity, TransactionPriority},
+    Perbill,
 };
+use std::cell::RefCell;
 
 type Balance = u64;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -22,6 +26,8 @@ type Block = frame_system::mocking::MockBlock<Test>;
 pub(crate) const RELAYER_A: u64 = 0x2;
 pub(crate) const RELAYER_B: u64 = 0x3;
 pub(crate) const RELAYER_C: u64 = 0x4;
+pub(crate) const DEPOSITOR: u64 = 0x6;
+pub(crate) const PAUSER: u64 = 0x7;
 pub(crate) const ENDOWED_BALANCE: u64 = 100_000_000;
 pub(crate) const TEST_THRESHOLD: u32 = 2;
 
@@ -34,8 +40,9 @@ frame_support::construct_runtime!(
     {
 
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-        Bridge: pallet_chainbridge::{Pallet, Call, Storage, Event<T>},
+        Bridge: pallet_chainbridge::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
         Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
+        Handler: handler::{Pallet, Call},
     }
 );
 
@@ -72,7 +79,7 @@ impl system::Config for Test {
     type BlockNumber = u64;
     type BlockWeights = ();
     type Call = Call;
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Event = Event;
     type Hash = H256;
     type Hashing = BlakeTwo256;
@@ -92,8 +99,23 @@ impl system::Config for Test {
 // Parameterize default test user identifier (with id 1)
 parameter_types! {
     pub const TestUserId: u64 = 1;
+    pub const TestPauserId: u64 = PAUSER;
     pub const TestChainId: ChainId = 5;
     pub const ProposalLifetime: u64 = 10;
+    pub const ProposalRetention: u64 = 5;
+    pub const PendingLifetime: u64 = 20;
+    pub const MaxRelayers: u32 = 5;
+    pub const MaxResourceMethodLen: u32 = 32;
+    pub const MaxRecipientLen: u32 = 32;
+    pub const MaxMetadataLen: u32 = 64;
+    pub const MaxChainNameLen: u32 = 16;
+    pub const MaxBatchSize: u32 = 4;
+    pub const MaxPrunePerBlock: u32 = 16;
+    pub const UnsignedPriority: TransactionPriority = 100;
+    pub const UnsignedLongevity: TransactionLongevity = 5;
+    pub const RelayerReward: u64 = 10;
+    pub const RelayerSlashFraction: Perbill = Perbill::from_percent(10);
+    pub const UnbondingPeriod: u64 = 5;
     pub const ChainBridgePalletId: PalletId = PalletId(*b"chnbrdge");
 }
 
@@ -103,6 +125,12 @@ impl SortedMembers<u64> for TestUserId {
     }
 }
 
+impl SortedMembers<u64> for TestPauserId {
+    fn sorted_members() -> Vec<u64> {
+        vec![PAUSER]
+    }
+}
+
 // Parameterize FRAME balances pallet
 parameter_types! {
     pub const ExistentialDeposit: u64 = 1;
@@ -110,19 +138,157 @@ parameter_types! {
 
 impl pallet_chainbridge::Config for Test {
     type AdminOrigin = EnsureSignedBy<TestUserId, u64>;
+    type PauseOrigin = EnsureSignedBy<TestPauserId, u64>;
     type ChainId = TestChainId;
     type Event = Event;
     type PalletId = ChainBridgePalletId;
     type Proposal = Call;
     type ProposalLifetime = ProposalLifetime;
+    type ProposalRetention = ProposalRetention;
+    type PendingLifetime = PendingLifetime;
+    type MaxRelayers = MaxRelayers;
+    type MaxResourceMethodLen = MaxResourceMethodLen;
+    type MaxRecipientLen = MaxRecipientLen;
+    type MaxMetadataLen = MaxMetadataLen;
+    type MaxChainNameLen = MaxChainNameLen;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxPrunePerBlock = MaxPrunePerBlock;
+    type Currency = Balances;
+    type FeeHandler = pallet_chainbridge::FeePot<Test>;
+    type RelayerReward = RelayerReward;
+    type RelayerBond = RelayerBond;
+    type SlashFraction = RelayerSlashFraction;
+    type Slash = ();
+    type UnbondingPeriod = UnbondingPeriod;
+    type OnEquivocation = (
+        pallet_chainbridge::SlashEquivocator<Test>,
+        pallet_chainbridge::RemoveEquivocator<Test>,
+    );
+    type ResourceCallMatcher = pallet_chainbridge::MatchCallName;
+    type ProposalFilter = ProposalFilter;
+    type RelayerSignature = TestSignature;
+    type RelayerPublic = UintAuthorityId;
+    type UnsignedPriority = UnsignedPriority;
+    type UnsignedLongevity = UnsignedLongevity;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = BenchmarkHelper;
+    type WeightInfo = ();
+}
+
+/// Signs attestations in benchmarks with test signatures, and proposes
+/// remarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_chainbridge::BenchmarkHelper<u64, TestSignature, Call>
+    for BenchmarkHelper
+{
+    fn create_signer(index: u32) -> u64 {
+        0x100 + index as u64
+    }
+
+    fn sign(signer: &u64, message: &[u8]) -> TestSignature {
+        TestSignature(*signer, message.to_vec())
+    }
+
+    fn make_proposal() -> Call {
+        Call::System(frame_system::Call::remark {
+            remark: vec![0; 32],
+        })
+    }
+
+    fn proposal_method() -> Vec<u8> {
+        b"System.remark".to_vec()
+    }
+}
+
+/// Stands in for a pallet handling inbound proposals, reporting the value
+/// it releases to the bridge.
+#[frame_support::pallet]
+pub mod handler {
+    use crate::{InboundVolume, ResourceId};
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use sp_core::U256;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + crate::Config {}
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Releases `amount`, counted against the inbound cap of the
+        /// resource the proposal was made under whatever `_r_id` says
+        #[pallet::weight(10_000)]
+        pub fn release(
+            origin: OriginFor<T>,
+            _r_id: ResourceId,
+            amount: u64,
+        ) -> DispatchResult {
+            crate::EnsureBridge::<T>::ensure_origin(origin)?;
+            <crate::Pallet<T>>::note_inbound(U256::from(amount))?;
+            Ok(())
+        }
+    }
+}
+
+impl handler::Config for Test {}
+
+/// Lets proposals call anything but the balances pallet, which would move
+/// funds of the bridge account.
+pub struct ProposalFilter;
+impl Contains<Call> for ProposalFilter {
+    fn contains(call: &Call) -> bool {
+        !matches!(call, Call::Balances(_))
+    }
+}
+
+thread_local! {
+    static RELAYER_BOND: RefCell<u64> = RefCell::new(0);
+}
+
+/// Relayer bond of the mock runtime, zero unless a test sets it.
+pub struct RelayerBond;
+impl RelayerBond {
+    pub fn set(bond: u64) {
+        RELAYER_BOND.with(|v| *v.borrow_mut() = bond);
+    }
+}
+impl Get<u64> for RelayerBond {
+    fn get() -> u64 {
+        RELAYER_BOND.with(|v| *v.borrow())
+    }
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::default()
+    let bridge_id = Bridge::account_id();
+    let mut t = system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![
+            (bridge_id, ENDOWED_BALANCE),
+            (DEPOSITOR, ENDOWED_BALANCE),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
+}
+
+// Build genesis storage with the bridge configured through its genesis config.
+pub fn new_test_ext_with_genesis(
+    bridge: pallet_chainbridge::GenesisConfig<Test>,
+) -> sp_io::TestExternalities {
+    let mut t = system::GenesisConfig::default()
         .build_storage::<Test>()
-        .unwrap()
-        .into()
+        .unwrap();
+    bridge.assimilate_storage(&mut t).unwrap();
+    t.into()
 }
 
 pub fn new_test_ext_initialized(
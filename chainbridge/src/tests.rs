@@ -1,15 +1,42 @@
 #![deny(warnings)]
+use crate::migrations::v2::OldProposalVotes;
 use crate::mock;
-use crate::mock::Bridge;
 use crate::mock::{
-    assert_events, new_test_ext, new_test_ext_initialized, Origin,
-    ProposalLifetime, Test, TestChainId, ENDOWED_BALANCE, RELAYER_A, RELAYER_B,
-    RELAYER_C, TEST_THRESHOLD,
+    assert_events, handler, new_test_ext, new_test_ext_initialized,
+    new_test_ext_with_genesis, MaxChainNameLen, MaxMetadataLen,
+    MaxPrunePerBlock, MaxRecipientLen, MaxRelayers, MaxResourceMethodLen,
+    Origin, PendingLifetime, ProposalLifetime, ProposalRetention, RelayerBond,
+    RelayerReward, RelayerSlashFraction, Test, TestChainId, UnbondingPeriod,
+    DEPOSITOR, ENDOWED_BALANCE, PAUSER, RELAYER_A, RELAYER_B, RELAYER_C,
+    TEST_THRESHOLD,
+};
+use crate::mock::{Balances, Bridge, System};
+use crate::pallet::PruneQueue;
+use crate::types::{
+    ChainInfo, ChainKind, InboundLimit, ProposalStatus, TransferLimit,
+    TransferUsage, VotePayload,
 };
-use crate::types::{ProposalStatus, ProposalVotes};
 use crate::RelayerThreshold;
-use crate::{derive_resource_id, Error, ResourceId};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    derive_resource_id, AttestationsOf, CheckRelayerVote, Error,
+    ProposalBatchOf, ProposalVotesOf, ResourceId, ValidityError, WeightInfo,
+    STORAGE_VERSION,
+};
+use codec::Encode;
+use frame_support::{
+    assert_err, assert_noop, assert_ok,
+    dispatch::{DispatchError, Dispatchable},
+    sp_runtime::{
+        testing::TestSignature,
+        traits::{BlakeTwo256, Hash, SignedExtension, ValidateUnsigned},
+        transaction_validity::{InvalidTransaction, TransactionSource},
+        PerThing, Perbill,
+    },
+    storage::migration::{get_storage_value, put_storage_value},
+    traits::{GetStorageVersion, Hooks, StorageVersion},
+    weights::{constants::RocksDbWeight, GetDispatchInfo, Pays, Weight},
+    Blake2_256, StorageHasher,
+};
 use sp_core::U256;
 
 #[test]
@@ -30,51 +57,91 @@ fn derive_ids() {
 
 #[test]
 fn complete_proposal_approved() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![3],
+    let mut prop = ProposalVotesOf::<Test> {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![3].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        epoch: 0,
+        threshold: 2,
+        relayer_count: 3,
+        resolved_at: None,
+        r_id: [0; 32],
     };
 
-    prop.try_to_complete(2, 3);
+    prop.try_to_complete(|_| true);
     assert_eq!(prop.status, ProposalStatus::Approved);
 }
 
 #[test]
 fn complete_proposal_rejected() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1],
-        votes_against: vec![2, 3],
+    let mut prop = ProposalVotesOf::<Test> {
+        votes_for: vec![1].try_into().unwrap(),
+        votes_against: vec![2, 3].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        epoch: 0,
+        threshold: 2,
+        relayer_count: 3,
+        resolved_at: None,
+        r_id: [0; 32],
     };
 
-    prop.try_to_complete(2, 3);
+    prop.try_to_complete(|_| true);
     assert_eq!(prop.status, ProposalStatus::Rejected);
 }
 
 #[test]
 fn complete_proposal_bad_threshold() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![],
+    let mut prop = ProposalVotesOf::<Test> {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![].try_into().unwrap(),
+        status: ProposalStatus::Initiated,
+        expiry: ProposalLifetime::get(),
+        epoch: 0,
+        threshold: 3,
+        relayer_count: 2,
+        resolved_at: None,
+        r_id: [0; 32],
+    };
+
+    prop.try_to_complete(|_| true);
+    assert_eq!(prop.status, ProposalStatus::Initiated);
+
+    let mut prop = ProposalVotesOf::<Test> {
+        votes_for: vec![].try_into().unwrap(),
+        votes_against: vec![1, 2].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        epoch: 0,
+        threshold: 3,
+        relayer_count: 2,
+        resolved_at: None,
+        r_id: [0; 32],
     };
 
-    prop.try_to_complete(3, 2);
+    prop.try_to_complete(|_| true);
     assert_eq!(prop.status, ProposalStatus::Initiated);
+}
 
-    let mut prop = ProposalVotes {
-        votes_for: vec![],
-        votes_against: vec![1, 2],
+#[test]
+fn complete_proposal_counts_members_only() {
+    let mut prop = ProposalVotesOf::<Test> {
+        votes_for: vec![1, 2].try_into().unwrap(),
+        votes_against: vec![3].try_into().unwrap(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
+        epoch: 0,
+        threshold: 2,
+        relayer_count: 3,
+        resolved_at: None,
+        r_id: [0; 32],
     };
 
-    prop.try_to_complete(3, 2);
+    prop.try_to_complete(|who| *who != 2);
     assert_eq!(prop.status, ProposalStatus::Initiated);
+    prop.try_to_complete(|_| true);
+    assert_eq!(prop.status, ProposalStatus::Approved);
 }
 
 #[test]
@@ -85,16 +152,34 @@ fn setup_resources() {
         let method2 = "Pallet.do_somethingElse".as_bytes().to_vec();
 
         assert_ok!(Bridge::set_resource(Origin::root(), id, method.clone()));
-        assert_eq!(Bridge::resources(id), Some(method));
+        assert_eq!(Bridge::resources(id).unwrap().into_inner(), method);
 
         assert_ok!(Bridge::set_resource(Origin::root(), id, method2.clone()));
-        assert_eq!(Bridge::resources(id), Some(method2));
+        assert_eq!(Bridge::resources(id).unwrap().into_inner(), method2);
 
         assert_ok!(Bridge::remove_resource(Origin::root(), id));
         assert_eq!(Bridge::resources(id), None);
     })
 }
 
+#[test]
+fn resource_method_too_long() {
+    new_test_ext().execute_with(|| {
+        let id: ResourceId = [1; 32];
+        let max_len = MaxResourceMethodLen::get() as usize;
+
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            id,
+            vec![b'a'; max_len]
+        ));
+        assert_noop!(
+            Bridge::set_resource(Origin::root(), id, vec![b'a'; max_len + 1]),
+            Error::<Test>::ResourceMethodTooLong
+        );
+    })
+}
+
 #[test]
 fn whitelist_chain() {
     new_test_ext().execute_with(|| {
@@ -112,6 +197,323 @@ fn whitelist_chain() {
     })
 }
 
+#[test]
+fn disable_enable_chain() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+
+    new_test_ext().execute_with(|| {
+        let transfer = || {
+            Bridge::transfer_generic(DEPOSITOR, dest_id, resource_id, vec![])
+        };
+        assert_noop!(
+            Bridge::disable_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_noop!(
+            Bridge::enable_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(transfer());
+        assert_eq!(Bridge::chain_nonce(dest_id), Some(1));
+
+        assert_noop!(
+            Bridge::disable_chain(Origin::signed(RELAYER_A), dest_id),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::disable_chain(Origin::root(), dest_id));
+        assert_events(vec![mock::Event::Bridge(
+            crate::Event::<Test>::ChainDisabled(dest_id),
+        )]);
+        assert!(!Bridge::chain_whitelisted(dest_id));
+        assert_noop!(transfer(), Error::<Test>::ChainNotWhitelisted);
+        assert_noop!(
+            Bridge::disable_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainAlreadyDisabled
+        );
+        // Whitelisting again would reset the nonce
+        assert_noop!(
+            Bridge::whitelist_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainAlreadyWhitelisted
+        );
+
+        // Enabling continues from the last nonce
+        assert_ok!(Bridge::enable_chain(Origin::root(), dest_id));
+        assert_events(vec![mock::Event::Bridge(
+            crate::Event::<Test>::ChainEnabled(dest_id),
+        )]);
+        assert_noop!(
+            Bridge::enable_chain(Origin::root(), dest_id),
+            Error::<Test>::ChainNotDisabled
+        );
+        assert_ok!(transfer());
+        assert_eq!(Bridge::chain_nonce(dest_id), Some(2));
+    })
+}
+
+#[test]
+fn register_chain() {
+    let dest_id = 2;
+    let resource_id = [1; 32];
+
+    new_test_ext().execute_with(|| {
+        let transfer = |to: Vec<u8>| {
+            Bridge::transfer_fungible(
+                DEPOSITOR,
+                dest_id,
+                resource_id,
+                to,
+                U256::one(),
+            )
+        };
+        assert_noop!(
+            Bridge::register_chain(
+                Origin::root(),
+                dest_id,
+                ChainKind::Evm,
+                Some(MaxRecipientLen::get() + 1),
+                b"Ethereum".to_vec()
+            ),
+            Error::<Test>::RecipientTooLong
+        );
+        assert_noop!(
+            Bridge::register_chain(
+                Origin::root(),
+                dest_id,
+                ChainKind::Evm,
+                Some(20),
+                vec![b'a'; MaxChainNameLen::get() as usize + 1]
+            ),
+            Error::<Test>::ChainNameTooLong
+        );
+
+        // Registering an unknown chain whitelists it
+        assert_ok!(Bridge::register_chain(
+            Origin::root(),
+            dest_id,
+            ChainKind::Evm,
+            Some(20),
+            b"Ethereum".to_vec()
+        ));
+        assert_events(vec![
+            mock::Event::Bridge(crate::Event::<Test>::ChainWhitelisted(
+                dest_id,
+            )),
+            mock::Event::Bridge(crate::Event::<Test>::ChainRegistered(
+                dest_id,
+                ChainKind::Evm,
+            )),
+        ]);
+        assert!(Bridge::chain_whitelisted(dest_id));
+
+        // Recipients must have the address length of the chain
+        assert_noop!(transfer(vec![1; 32]), Error::<Test>::InvalidRecipient);
+        assert_noop!(
+            Bridge::transfer_nonfungible(
+                DEPOSITOR,
+                dest_id,
+                resource_id,
+                vec![1],
+                vec![1; 19],
+                vec![]
+            ),
+            Error::<Test>::InvalidRecipient
+        );
+        assert_ok!(transfer(vec![1; 20]));
+
+        // Updating the metadata keeps the nonce and the enabled flag
+        assert_ok!(Bridge::disable_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::register_chain(
+            Origin::root(),
+            dest_id,
+            ChainKind::Substrate,
+            None,
+            b"Westend".to_vec()
+        ));
+        assert_events(vec![mock::Event::Bridge(
+            crate::Event::<Test>::ChainRegistered(
+                dest_id,
+                ChainKind::Substrate,
+            ),
+        )]);
+        assert_eq!(
+            Bridge::chains(dest_id),
+            Some(ChainInfo {
+                nonce: 1,
+                kind: ChainKind::Substrate,
+                recipient_len: None,
+                enabled: false,
+                name: b"Westend".to_vec().try_into().unwrap(),
+            })
+        );
+    })
+}
+
+#[test]
+fn migrate_chain_nonces() {
+    new_test_ext().execute_with(|| {
+        let key = |id: u8| Blake2_256::hash(&id.encode());
+        StorageVersion::new(0).put::<Bridge>();
+        put_storage_value(b"Bridge", b"ChainNonces", &key(1), Some(0u64));
+        put_storage_value(b"Bridge", b"ChainNonces", &key(2), Some(7u64));
+
+        Bridge::on_runtime_upgrade();
+
+        assert_eq!(Bridge::on_chain_storage_version(), STORAGE_VERSION);
+        assert_eq!(Bridge::chain_nonce(1), Some(0));
+        assert_eq!(Bridge::chain_nonce(2), Some(7));
+        assert_eq!(Bridge::chain_nonce(3), None);
+        assert!(Bridge::chain_whitelisted(1));
+        assert!(Bridge::chain_whitelisted(2));
+        assert_eq!(Bridge::chains(1).unwrap().kind, ChainKind::Other);
+    })
+}
+
+#[test]
+fn migrate_legacy_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let votes = |status| OldProposalVotes::<u64, u64, MaxRelayers> {
+                votes_for: vec![RELAYER_A, RELAYER_B].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
+                status,
+                expiry: ProposalLifetime::get() + 1,
+            };
+            let votes_key = |nonce: u64| {
+                [
+                    Blake2_256::hash(&src_id.encode()),
+                    Blake2_256::hash(&(nonce, proposal.clone()).encode()),
+                ]
+                .concat()
+            };
+            // Votes as stored before any migration, without a prune queue
+            StorageVersion::new(0).put::<Bridge>();
+            put_storage_value(
+                b"Bridge",
+                b"Votes",
+                &votes_key(1),
+                votes(ProposalStatus::Approved),
+            );
+            put_storage_value(
+                b"Bridge",
+                b"Votes",
+                &votes_key(2),
+                votes(ProposalStatus::Initiated),
+            );
+
+            Bridge::on_runtime_upgrade();
+
+            assert_eq!(Bridge::on_chain_storage_version(), STORAGE_VERSION);
+            for nonce in [1, 2] {
+                assert!(get_storage_value::<
+                    OldProposalVotes<u64, u64, MaxRelayers>,
+                >(
+                    b"Bridge", b"Votes", &votes_key(nonce)
+                )
+                .is_none());
+            }
+            let acknowledge = |relayer, nonce| {
+                Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    nonce,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone()),
+                )
+            };
+
+            // The executed proposal is not executed again
+            assert_ok!(acknowledge(RELAYER_A, 1));
+            assert_ok!(acknowledge(RELAYER_B, 1));
+            assert!(Bridge::nonce_executed(src_id, 1));
+            assert_eq!(
+                Bridge::get_votes(src_id, (1, proposal.clone()))
+                    .unwrap()
+                    .status,
+                ProposalStatus::ExecutionFailed(
+                    Error::<Test>::NonceAlreadyExecuted.into()
+                )
+            );
+            assert_events(vec![
+                mock::Event::Bridge(crate::Event::<Test>::VoteFor(
+                    src_id, 1, RELAYER_B,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalApproved(
+                    src_id, 1,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalFailed(
+                    src_id, 1,
+                )),
+            ]);
+
+            // The open proposal is voted on again
+            assert_ok!(acknowledge(RELAYER_A, 2));
+            assert_ok!(acknowledge(RELAYER_B, 2));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalSucceeded(src_id, 2),
+            )]);
+        })
+}
+
+#[test]
+fn genesis_config() {
+    let r_id = derive_resource_id(1, b"remark");
+    new_test_ext_with_genesis(crate::GenesisConfig::<Test> {
+        relayers: vec![RELAYER_A, RELAYER_B, RELAYER_C],
+        threshold: TEST_THRESHOLD,
+        chains: vec![(1, 0), (2, 7)],
+        resources: vec![(r_id, b"System.remark".to_vec())],
+    })
+    .execute_with(|| {
+        assert_eq!(Bridge::relayer_threshold(), TEST_THRESHOLD);
+        assert_eq!(Bridge::relayer_count(), 3);
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(Bridge::is_relayer(&RELAYER_B));
+        assert!(Bridge::is_relayer(&RELAYER_C));
+        assert_eq!(Bridge::chain_nonce(1), Some(0));
+        assert_eq!(Bridge::chain_nonce(2), Some(7));
+        assert!(!Bridge::chain_whitelisted(3));
+        assert_eq!(
+            Bridge::resources(r_id).unwrap().into_inner(),
+            b"System.remark".to_vec()
+        );
+
+        // Deposits continue from the configured nonce
+        assert_ok!(Bridge::transfer_generic(DEPOSITOR, 2, r_id, vec![]));
+        assert_eq!(Bridge::chain_nonce(2), Some(8));
+    })
+}
+
+#[test]
+#[should_panic(
+    expected = "Relayer threshold is greater than the number of relayers"
+)]
+fn genesis_config_rejects_unreachable_threshold() {
+    new_test_ext_with_genesis(crate::GenesisConfig::<Test> {
+        relayers: vec![RELAYER_A],
+        threshold: TEST_THRESHOLD,
+        chains: vec![],
+        resources: vec![],
+    });
+}
+
+#[test]
+#[should_panic(expected = "Cannot whitelist this chain")]
+fn genesis_config_rejects_own_chain() {
+    new_test_ext_with_genesis(crate::GenesisConfig::<Test> {
+        relayers: vec![],
+        threshold: 1,
+        chains: vec![(TestChainId::get(), 0)],
+        resources: vec![],
+    });
+}
+
 #[test]
 fn set_get_threshold() {
     new_test_ext().execute_with(|| {
@@ -134,6 +536,95 @@ fn set_get_threshold() {
     })
 }
 
+#[test]
+fn threshold_must_be_reachable() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            assert_noop!(
+                Bridge::set_threshold(Origin::root(), 4),
+                Error::<Test>::ThresholdUnreachable
+            );
+            assert_ok!(Bridge::set_threshold(Origin::root(), 3));
+            assert_noop!(
+                Bridge::remove_relayer(Origin::root(), RELAYER_A),
+                Error::<Test>::ThresholdUnreachable
+            );
+
+            assert_ok!(Bridge::set_threshold(Origin::root(), 2));
+            assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+            assert_noop!(
+                Bridge::remove_relayer(Origin::root(), RELAYER_B),
+                Error::<Test>::ThresholdUnreachable
+            );
+        })
+}
+
+#[test]
+fn threshold_ratio_follows_relayer_set() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            assert_noop!(
+                Bridge::set_threshold_ratio(
+                    Origin::signed(RELAYER_A),
+                    Perbill::from_percent(50)
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                Bridge::set_threshold_ratio(Origin::root(), Perbill::zero()),
+                Error::<Test>::InvalidThreshold
+            );
+
+            let ratio = Perbill::from_percent(67);
+            assert_ok!(Bridge::set_threshold_ratio(Origin::root(), ratio));
+            assert_eq!(Bridge::relayer_threshold_ratio(), Some(ratio));
+            assert_eq!(Bridge::current_threshold(), 3);
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::RelayerThresholdRatioChanged(ratio),
+            )]);
+
+            // Every relayer has to vote
+            let proposal = make_proposal(vec![10]);
+            for relayer in [RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ));
+            }
+            assert!(!Bridge::nonce_executed(src_id, 1));
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            assert!(Bridge::nonce_executed(src_id, 1));
+
+            // The threshold follows the relayer set down to a single relayer
+            assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+            assert_eq!(Bridge::current_threshold(), 2);
+            assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+            assert_eq!(Bridge::current_threshold(), 1);
+            assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+            assert_eq!(Bridge::current_threshold(), 2);
+
+            // An absolute threshold replaces the ratio
+            assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+            assert_eq!(Bridge::relayer_threshold_ratio(), None);
+            assert_eq!(Bridge::current_threshold(), 1);
+        })
+}
+
 #[test]
 fn asset_transfer_success() {
     new_test_ext().execute_with(|| {
@@ -148,6 +639,7 @@ fn asset_transfer_success() {
 
         assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id.clone()));
         assert_ok!(Bridge::transfer_fungible(
+            DEPOSITOR,
             dest_id.clone(),
             resource_id.clone(),
             to.clone(),
@@ -167,6 +659,7 @@ fn asset_transfer_success() {
         ]);
 
         assert_ok!(Bridge::transfer_nonfungible(
+            DEPOSITOR,
             dest_id.clone(),
             resource_id.clone(),
             token_id.clone(),
@@ -185,6 +678,7 @@ fn asset_transfer_success() {
         )]);
 
         assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
             dest_id.clone(),
             resource_id.clone(),
             metadata.clone()
@@ -214,6 +708,7 @@ fn asset_transfer_invalid_chain() {
 
         assert_noop!(
             Bridge::transfer_fungible(
+                DEPOSITOR,
                 bad_dest_id,
                 resource_id.clone(),
                 vec![],
@@ -224,6 +719,7 @@ fn asset_transfer_invalid_chain() {
 
         assert_noop!(
             Bridge::transfer_nonfungible(
+                DEPOSITOR,
                 bad_dest_id,
                 resource_id.clone(),
                 vec![],
@@ -234,64 +730,388 @@ fn asset_transfer_invalid_chain() {
         );
 
         assert_noop!(
-            Bridge::transfer_generic(bad_dest_id, resource_id.clone(), vec![]),
+            Bridge::transfer_generic(
+                DEPOSITOR,
+                bad_dest_id,
+                resource_id.clone(),
+                vec![]
+            ),
             Error::<Test>::ChainNotWhitelisted
         );
     })
 }
 
 #[test]
-fn add_remove_relayer() {
+fn asset_transfer_too_long() {
     new_test_ext().execute_with(|| {
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
-        assert_eq!(Bridge::relayer_count(), 0);
-
-        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
-        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
-        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
-        assert_eq!(Bridge::relayer_count(), 3);
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let long_to = vec![2; MaxRecipientLen::get() as usize + 1];
+        let long_metadata = vec![3; MaxMetadataLen::get() as usize + 1];
 
-        // Already exists
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
         assert_noop!(
-            Bridge::add_relayer(Origin::root(), RELAYER_A),
-            Error::<Test>::RelayerAlreadyExists
+            Bridge::transfer_fungible(
+                DEPOSITOR,
+                dest_id,
+                resource_id,
+                long_to.clone(),
+                U256::one()
+            ),
+            Error::<Test>::RecipientTooLong
+        );
+        assert_noop!(
+            Bridge::transfer_nonfungible(
+                DEPOSITOR,
+                dest_id,
+                resource_id,
+                vec![1],
+                long_to,
+                vec![]
+            ),
+            Error::<Test>::RecipientTooLong
+        );
+        assert_noop!(
+            Bridge::transfer_nonfungible(
+                DEPOSITOR,
+                dest_id,
+                resource_id,
+                vec![1],
+                vec![2],
+                long_metadata.clone()
+            ),
+            Error::<Test>::MetadataTooLong
+        );
+        assert_noop!(
+            Bridge::transfer_generic(
+                DEPOSITOR,
+                dest_id,
+                resource_id,
+                long_metadata
+            ),
+            Error::<Test>::MetadataTooLong
         );
+        assert_eq!(Bridge::chain_nonce(dest_id), Some(0));
+    })
+}
+
+#[test]
+fn set_fee() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
 
-        // Confirm removal
-        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
-        assert_eq!(Bridge::relayer_count(), 2);
         assert_noop!(
-            Bridge::remove_relayer(Origin::root(), RELAYER_B),
-            Error::<Test>::RelayerInvalid
+            Bridge::set_fee(
+                Origin::signed(RELAYER_A),
+                dest_id,
+                resource_id,
+                10
+            ),
+            DispatchError::BadOrigin
         );
-        assert_eq!(Bridge::relayer_count(), 2);
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, resource_id, 10));
+        assert_eq!(Bridge::get_fee(dest_id, resource_id), 10);
+        assert_eq!(Bridge::get_fee(dest_id, [2; 32]), 0);
+
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, resource_id, 0));
+        assert_eq!(Bridge::get_fee(dest_id, resource_id), 0);
 
         assert_events(vec![
-            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(RELAYER_A)),
-            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(RELAYER_B)),
-            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(RELAYER_C)),
-            mock::Event::Bridge(crate::Event::<Test>::RelayerRemoved(
-                RELAYER_B,
+            mock::Event::Bridge(crate::Event::<Test>::FeeChanged(
+                dest_id,
+                resource_id,
+                10,
+            )),
+            mock::Event::Bridge(crate::Event::<Test>::FeeChanged(
+                dest_id,
+                resource_id,
+                0,
             )),
         ]);
     })
 }
 
-fn make_proposal(r: Vec<u8>) -> mock::Call {
-    mock::Call::System(frame_system::Call::remark { remark: r })
-}
-
 #[test]
-fn create_sucessful_proposal() {
-    let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"remark");
+fn asset_transfer_charges_fee() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let fee = 1_000;
 
-    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
-        .execute_with(|| {
-            let prop_id = 1;
-            let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, resource_id, fee));
 
-            // Create proposal (& vote)
+        assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
+            dest_id,
+            resource_id,
+            vec![]
+        ));
+        assert_eq!(Balances::free_balance(DEPOSITOR), ENDOWED_BALANCE - fee);
+        assert_eq!(Balances::free_balance(Bridge::fee_pot_account()), fee);
+
+        // Transfers of other resources are free
+        assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
+            dest_id,
+            [2; 32],
+            vec![]
+        ));
+        assert_eq!(Balances::free_balance(DEPOSITOR), ENDOWED_BALANCE - fee);
+
+        // The depositor must be able to pay the fee
+        assert_noop!(
+            Bridge::transfer_generic(RELAYER_A, dest_id, resource_id, vec![]),
+            Error::<Test>::InsufficientFee
+        );
+        assert_eq!(Bridge::chain_nonce(dest_id), Some(2));
+    })
+}
+
+#[test]
+fn asset_transfer_limits() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let limit = TransferLimit {
+            max_per_transfer: Some(100.into()),
+            max_per_window: Some(150.into()),
+            window: 10,
+            max_deposits_per_block: Some(2),
+        };
+        let transfer = |amount: u64| {
+            Bridge::transfer_fungible(
+                DEPOSITOR,
+                dest_id,
+                resource_id,
+                vec![2],
+                amount.into(),
+            )
+        };
+
+        System::set_block_number(1);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_noop!(
+            Bridge::set_transfer_limit(
+                Origin::signed(RELAYER_A),
+                resource_id,
+                Some(limit.clone())
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::set_transfer_limit(
+            Origin::root(),
+            resource_id,
+            Some(limit.clone())
+        ));
+        assert_events(vec![mock::Event::Bridge(
+            crate::Event::<Test>::TransferLimitChanged(
+                resource_id,
+                Some(limit),
+            ),
+        )]);
+
+        assert_noop!(transfer(101), Error::<Test>::TransferAmountTooLarge);
+        assert_ok!(transfer(100));
+        assert_noop!(transfer(60), Error::<Test>::TransferVolumeExceeded);
+        assert_ok!(transfer(50));
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, dest_id, resource_id, vec![]),
+            Error::<Test>::TooManyDeposits
+        );
+        assert_eq!(
+            Bridge::current_usage(resource_id),
+            Some(TransferUsage {
+                volume: 150.into(),
+                deposits: 2,
+                updated_at: 1,
+            })
+        );
+
+        // Other resources are not limited
+        assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
+            dest_id,
+            [2; 32],
+            vec![]
+        ));
+        assert_eq!(Bridge::current_usage([2; 32]), None);
+
+        // The volume drains over the window
+        System::set_block_number(3);
+        assert_eq!(
+            Bridge::current_usage(resource_id),
+            Some(TransferUsage {
+                volume: 120.into(),
+                deposits: 0,
+                updated_at: 3,
+            })
+        );
+        assert_ok!(transfer(30));
+        assert_noop!(transfer(1), Error::<Test>::TransferVolumeExceeded);
+
+        // Removing the limit clears the usage
+        assert_ok!(Bridge::set_transfer_limit(
+            Origin::root(),
+            resource_id,
+            None
+        ));
+        assert_eq!(Bridge::transfer_usage(resource_id), None);
+        assert_ok!(transfer(1_000));
+    })
+}
+
+#[test]
+fn pause_bridge_and_chains() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let vote = |relayer| {
+                Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone()),
+                )
+            };
+            let transfer =
+                || Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![]);
+
+            // Only the pause origin or root can pause
+            assert_noop!(
+                Bridge::pause_bridge(Origin::signed(RELAYER_A)),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Bridge::pause_bridge(Origin::signed(PAUSER)));
+            assert!(Bridge::bridge_paused());
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::BridgePaused,
+            )]);
+
+            assert_noop!(vote(RELAYER_A), Error::<Test>::BridgePaused);
+            assert_noop!(
+                Bridge::reject_proposal(
+                    Origin::signed(RELAYER_A),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<Test>::BridgePaused
+            );
+            assert_noop!(
+                Bridge::eval_vote_state(
+                    Origin::signed(RELAYER_A),
+                    1,
+                    src_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<Test>::BridgePaused
+            );
+            assert_noop!(transfer(), Error::<Test>::BridgePaused);
+
+            assert_ok!(Bridge::unpause_bridge(Origin::root()));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::BridgeUnpaused,
+            )]);
+            assert_ok!(vote(RELAYER_A));
+            assert_ok!(transfer());
+
+            // Pausing a chain leaves the others running
+            assert_noop!(
+                Bridge::pause_chain(Origin::signed(RELAYER_A), src_id),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Bridge::pause_chain(Origin::signed(PAUSER), src_id));
+            assert!(Bridge::chain_paused(src_id));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ChainPaused(src_id),
+            )]);
+            assert_noop!(vote(RELAYER_B), Error::<Test>::BridgePaused);
+            assert_noop!(transfer(), Error::<Test>::BridgePaused);
+            assert_ok!(Bridge::whitelist_chain(Origin::root(), 2));
+            assert_ok!(Bridge::transfer_generic(DEPOSITOR, 2, r_id, vec![]));
+
+            assert_ok!(Bridge::unpause_chain(Origin::signed(PAUSER), src_id));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ChainUnpaused(src_id),
+            )]);
+            assert_ok!(vote(RELAYER_B));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalSucceeded(src_id, 1),
+            )]);
+        })
+}
+
+#[test]
+fn add_remove_relayer() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
+        assert_eq!(Bridge::relayer_count(), 0);
+
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+        assert_eq!(Bridge::relayer_count(), 3);
+
+        // Already exists
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::RelayerAlreadyExists
+        );
+
+        // Confirm removal
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(Bridge::relayer_count(), 2);
+        assert_noop!(
+            Bridge::remove_relayer(Origin::root(), RELAYER_B),
+            Error::<Test>::RelayerInvalid
+        );
+        assert_eq!(Bridge::relayer_count(), 2);
+
+        // Relayer set is full
+        for relayer in 10..(10 + MaxRelayers::get() as u64 - 2) {
+            assert_ok!(Bridge::add_relayer(Origin::root(), relayer));
+        }
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_B),
+            Error::<Test>::TooManyRelayers
+        );
+
+        assert_events(vec![
+            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(RELAYER_A)),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(RELAYER_B)),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(RELAYER_C)),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerRemoved(
+                RELAYER_B,
+            )),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(10)),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(11)),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerAdded(12)),
+        ]);
+    })
+}
+
+fn make_proposal(r: Vec<u8>) -> mock::Call {
+    mock::Call::System(frame_system::Call::remark { remark: r })
+}
+
+#[test]
+fn create_sucessful_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            let proposal = make_proposal(vec![10]);
+
+            // Create proposal (& vote)
             assert_ok!(Bridge::acknowledge_proposal(
                 Origin::signed(RELAYER_A),
                 prop_id,
@@ -302,11 +1122,16 @@ fn create_sucessful_proposal() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -321,11 +1146,16 @@ fn create_sucessful_proposal() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![RELAYER_B],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![RELAYER_B].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -340,11 +1170,16 @@ fn create_sucessful_proposal() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A, RELAYER_C],
-                votes_against: vec![RELAYER_B],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A, RELAYER_C].try_into().unwrap(),
+                votes_against: vec![RELAYER_B].try_into().unwrap(),
                 status: ProposalStatus::Approved,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: Some(1),
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -389,11 +1224,16 @@ fn create_unsucessful_proposal() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -408,11 +1248,16 @@ fn create_unsucessful_proposal() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![RELAYER_B],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![RELAYER_B].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -427,11 +1272,16 @@ fn create_unsucessful_proposal() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![RELAYER_B, RELAYER_C],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![RELAYER_B, RELAYER_C].try_into().unwrap(),
                 status: ProposalStatus::Rejected,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: Some(1),
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -459,7 +1309,7 @@ fn create_unsucessful_proposal() {
 }
 
 #[test]
-fn execute_after_threshold_change() {
+fn threshold_change_keeps_proposal_threshold() {
     let src_id = 1;
     let r_id = derive_resource_id(src_id, b"transfer");
 
@@ -476,45 +1326,59 @@ fn execute_after_threshold_change() {
                 r_id,
                 Box::new(proposal.clone())
             ));
-            let prop =
-                Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
-                    .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
-            assert_eq!(prop, expected);
+            assert_eq!(
+                Bridge::get_votes(src_id, (prop_id, proposal.clone())),
+                Some(expected.clone())
+            );
 
             // Change threshold
             assert_ok!(Bridge::set_threshold(Origin::root(), 1));
 
-            // Attempt to execute
+            // The proposal is still tallied against its own threshold
             assert_ok!(Bridge::eval_vote_state(
                 Origin::signed(RELAYER_A),
                 prop_id,
                 src_id,
                 Box::new(proposal.clone())
             ));
+            assert_eq!(
+                Bridge::get_votes(src_id, (prop_id, proposal.clone())),
+                Some(expected)
+            );
 
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
             let prop =
-                Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
-                    .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+                Bridge::get_votes(src_id, (prop_id, proposal.clone())).unwrap();
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A, RELAYER_B].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
                 status: ProposalStatus::Approved,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: Some(1),
+                r_id,
             };
             assert_eq!(prop, expected);
 
-            assert_eq!(mock::Balances::free_balance(RELAYER_B), 0);
-            assert_eq!(
-                mock::Balances::free_balance(Bridge::account_id()),
-                ENDOWED_BALANCE
-            );
-
             assert_events(vec![
                 mock::Event::Bridge(crate::Event::<Test>::VoteFor(
                     src_id, prop_id, RELAYER_A,
@@ -522,6 +1386,9 @@ fn execute_after_threshold_change() {
                 mock::Event::Bridge(
                     crate::Event::<Test>::RelayerThresholdChanged(1),
                 ),
+                mock::Event::Bridge(crate::Event::<Test>::VoteFor(
+                    src_id, prop_id, RELAYER_B,
+                )),
                 mock::Event::Bridge(crate::Event::<Test>::ProposalApproved(
                     src_id, prop_id,
                 )),
@@ -532,6 +1399,83 @@ fn execute_after_threshold_change() {
         })
 }
 
+#[test]
+fn votes_count_for_proposal_epoch_only() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+    let late_relayer = 0x10;
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            let proposal = make_proposal(vec![11]);
+
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            assert_eq!(Bridge::relayer_epoch(), 3);
+
+            // Relayers added since the proposal was created cannot vote on it
+            assert_ok!(Bridge::add_relayer(Origin::root(), late_relayer));
+            assert_eq!(Bridge::relayer_epoch(), 4);
+            assert_eq!(Bridge::relayer_since(late_relayer), 4);
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(late_relayer),
+                    prop_id,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<Test>::NotEpochMember
+            );
+
+            // Votes of removed relayers no longer count
+            assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+            assert_eq!(Bridge::relayer_epoch(), 5);
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            let prop =
+                Bridge::get_votes(src_id, (prop_id, proposal.clone())).unwrap();
+            assert_eq!(prop.status, ProposalStatus::Initiated);
+            assert_eq!(
+                (prop.epoch, prop.threshold, prop.relayer_count),
+                (3, TEST_THRESHOLD, 3)
+            );
+
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            let prop =
+                Bridge::get_votes(src_id, (prop_id, proposal.clone())).unwrap();
+            assert_eq!(prop.status, ProposalStatus::Approved);
+
+            // Nor are removed relayers rewarded for them
+            assert_eq!(Bridge::pending_rewards(RELAYER_A), 0);
+            assert_eq!(
+                Bridge::pending_rewards(RELAYER_B),
+                RelayerReward::get()
+            );
+            assert_eq!(
+                Bridge::pending_rewards(RELAYER_C),
+                RelayerReward::get()
+            );
+        })
+}
+
 #[test]
 fn proposal_expires() {
     let src_id = 1;
@@ -553,11 +1497,16 @@ fn proposal_expires() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -580,11 +1529,16 @@ fn proposal_expires() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -601,11 +1555,16 @@ fn proposal_expires() {
             let prop =
                 Bridge::get_votes(src_id, (prop_id.clone(), proposal.clone()))
                     .unwrap();
-            let expected = ProposalVotes {
-                votes_for: vec![RELAYER_A],
-                votes_against: vec![],
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
                 status: ProposalStatus::Initiated,
                 expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: None,
+                r_id,
             };
             assert_eq!(prop, expected);
 
@@ -614,3 +1573,1834 @@ fn proposal_expires() {
             )]);
         })
 }
+
+#[test]
+fn relayers_rewarded_for_winning_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let reward = RelayerReward::get();
+            let approved = make_proposal(vec![10]);
+            let rejected = make_proposal(vec![11]);
+
+            // A and B approve the first proposal, C's late vote is refused
+            for relayer in [RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(approved.clone())
+                ));
+            }
+            assert_noop!(
+                Bridge::reject_proposal(
+                    Origin::signed(RELAYER_C),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(approved.clone())
+                ),
+                Error::<Test>::ProposalAlreadyComplete
+            );
+
+            // B votes for the second proposal, A and C reject it
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                2,
+                src_id,
+                r_id,
+                Box::new(rejected.clone())
+            ));
+            for relayer in [RELAYER_A, RELAYER_C] {
+                assert_ok!(Bridge::reject_proposal(
+                    Origin::signed(relayer),
+                    2,
+                    src_id,
+                    r_id,
+                    Box::new(rejected.clone())
+                ));
+            }
+
+            assert_eq!(Bridge::pending_rewards(RELAYER_A), 2 * reward);
+            assert_eq!(Bridge::pending_rewards(RELAYER_B), reward);
+            assert_eq!(Bridge::pending_rewards(RELAYER_C), reward);
+
+            // Rewards are paid out of the fee pot
+            assert_noop!(
+                Bridge::claim_rewards(Origin::signed(RELAYER_A)),
+                Error::<Test>::InsufficientRewardFunds
+            );
+            Balances::make_free_balance_be(
+                &Bridge::fee_pot_account(),
+                ENDOWED_BALANCE,
+            );
+            assert_ok!(Bridge::claim_rewards(Origin::signed(RELAYER_A)));
+            assert_eq!(Balances::free_balance(RELAYER_A), 2 * reward);
+            assert_eq!(Bridge::pending_rewards(RELAYER_A), 0);
+            assert_noop!(
+                Bridge::claim_rewards(Origin::signed(RELAYER_A)),
+                Error::<Test>::NoRewards
+            );
+
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::RewardsClaimed(RELAYER_A, 2 * reward),
+            )]);
+        })
+}
+
+#[test]
+fn relayer_bond_reserved_and_unbonded() {
+    new_test_ext().execute_with(|| {
+        let bond = 100;
+        RelayerBond::set(bond);
+        Balances::make_free_balance_be(&RELAYER_A, ENDOWED_BALANCE);
+
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::relayer_bond(RELAYER_A), bond);
+        assert_eq!(Balances::reserved_balance(RELAYER_A), bond);
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_B),
+            Error::<Test>::InsufficientBond
+        );
+
+        // The bond stays reserved for the unbonding period
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        let unlock_at = System::block_number() + UnbondingPeriod::get();
+        assert_eq!(Bridge::relayer_bond(RELAYER_A), 0);
+        assert_eq!(Bridge::unbonding(RELAYER_A), Some((bond, unlock_at)));
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)),
+            Error::<Test>::BondStillLocked
+        );
+
+        System::set_block_number(unlock_at);
+        assert_ok!(Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 0);
+        assert_eq!(Balances::free_balance(RELAYER_A), ENDOWED_BALANCE);
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)),
+            Error::<Test>::NotUnbonding
+        );
+
+        assert_events(vec![mock::Event::Bridge(
+            crate::Event::<Test>::BondWithdrawn(RELAYER_A, bond),
+        )]);
+    })
+}
+
+#[test]
+fn losing_votes_are_slashed() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext().execute_with(|| {
+        let bond = 100;
+        RelayerBond::set(bond);
+        for relayer in [RELAYER_A, RELAYER_B, RELAYER_C] {
+            Balances::make_free_balance_be(&relayer, ENDOWED_BALANCE);
+            assert_ok!(Bridge::add_relayer(Origin::root(), relayer));
+        }
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            b"System.remark".to_vec()
+        ));
+
+        let proposal = make_proposal(vec![10]);
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_C),
+            1,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        for relayer in [RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+        }
+
+        let slashed = RelayerSlashFraction::get() * bond;
+        assert_eq!(Bridge::relayer_bond(RELAYER_A), bond);
+        assert_eq!(Bridge::relayer_bond(RELAYER_B), bond);
+        assert_eq!(Bridge::relayer_bond(RELAYER_C), bond - slashed);
+        assert_eq!(Balances::reserved_balance(RELAYER_C), bond - slashed);
+        assert!(System::events().iter().any(|record| record.event
+            == mock::Event::Bridge(crate::Event::<Test>::RelayerSlashed(
+                RELAYER_C, slashed,
+            ))));
+    })
+}
+
+#[test]
+fn equivocating_relayer_is_slashed_and_removed() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext().execute_with(|| {
+        let bond = 100;
+        RelayerBond::set(bond);
+        for relayer in [RELAYER_A, RELAYER_B, RELAYER_C] {
+            Balances::make_free_balance_be(&relayer, ENDOWED_BALANCE);
+            assert_ok!(Bridge::add_relayer(Origin::root(), relayer));
+        }
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            b"System.remark".to_vec()
+        ));
+        System::set_block_number(1);
+
+        let proposal = make_proposal(vec![10]);
+        let conflicting = make_proposal(vec![11]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        // The conflicting vote fails and is not counted, but recorded
+        assert_err!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                Box::new(conflicting.clone())
+            ),
+            Error::<Test>::ConflictingVote
+        );
+        assert_eq!(Bridge::get_votes(src_id, (1, conflicting)), None);
+        assert_eq!(Bridge::equivocation(RELAYER_A, (src_id, 1)), Some(1));
+
+        let slashed = RelayerSlashFraction::get() * bond;
+        assert_events(vec![
+            mock::Event::Bridge(crate::Event::<Test>::RelayerSlashed(
+                RELAYER_A, slashed,
+            )),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerRemoved(
+                RELAYER_A,
+            )),
+            mock::Event::Bridge(crate::Event::<Test>::RelayerEquivocated(
+                RELAYER_A, src_id, 1,
+            )),
+        ]);
+        assert!(!Bridge::is_relayer(&RELAYER_A));
+        assert_eq!(
+            Bridge::unbonding(RELAYER_A),
+            Some((bond - slashed, 1 + UnbondingPeriod::get()))
+        );
+
+        // The vote cast before equivocating no longer counts
+        for relayer in [RELAYER_B, RELAYER_C] {
+            assert_eq!(
+                Bridge::get_votes(src_id, (1, proposal.clone()))
+                    .unwrap()
+                    .status,
+                ProposalStatus::Initiated
+            );
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+        }
+        assert_eq!(
+            Bridge::get_votes(src_id, (1, proposal)).unwrap().status,
+            ProposalStatus::Approved
+        );
+    })
+}
+
+#[test]
+fn voting_again_after_expiry_is_not_equivocation() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let expired = make_proposal(vec![10]);
+            let proposal = make_proposal(vec![11]);
+            let acknowledge = |relayer, call: &mock::Call| {
+                Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(call.clone()),
+                )
+            };
+
+            assert_ok!(acknowledge(RELAYER_A, &expired));
+            assert_noop!(
+                acknowledge(RELAYER_B, &proposal),
+                Error::<Test>::NonceInUse
+            );
+
+            // Expired but not pruned yet, the proposal no longer holds the
+            // nonce nor binds the vote of its relayer
+            System::set_block_number(1 + ProposalLifetime::get());
+            assert!(Bridge::get_votes(src_id, (1, expired)).is_some());
+            assert_ok!(acknowledge(RELAYER_A, &proposal));
+            assert_eq!(Bridge::equivocation(RELAYER_A, (src_id, 1)), None);
+            assert!(Bridge::is_relayer(&RELAYER_A));
+            assert_ok!(acknowledge(RELAYER_B, &proposal));
+            assert!(Bridge::nonce_executed(src_id, 1));
+        })
+}
+
+#[test]
+fn removing_equivocator_keeps_threshold_reachable() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext().execute_with(|| {
+        for relayer in [RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::add_relayer(Origin::root(), relayer));
+        }
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            b"System.remark".to_vec()
+        ));
+        System::set_block_number(1);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            Box::new(make_proposal(vec![10]))
+        ));
+        assert_err!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                Box::new(make_proposal(vec![11]))
+            ),
+            Error::<Test>::ConflictingVote
+        );
+
+        // The relayer left alone can still meet the threshold
+        assert!(!Bridge::is_relayer(&RELAYER_A));
+        assert_eq!(Bridge::relayer_count(), 1);
+        assert_eq!(Bridge::relayer_threshold(), 1);
+        assert!(System::events().iter().any(|record| record.event
+            == mock::Event::Bridge(
+                crate::Event::<Test>::RelayerThresholdChanged(1)
+            )));
+    })
+}
+
+#[test]
+fn proposal_weight_includes_proposed_call() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            let proposal = make_proposal(vec![10; 100]);
+            let call_weight = proposal.get_dispatch_info().weight;
+            let vote_weight = <() as WeightInfo>::acknowledge_proposal(3);
+            let max_vote_weight =
+                <() as WeightInfo>::acknowledge_proposal(MaxRelayers::get());
+
+            let vote = mock::Call::Bridge(crate::Call::acknowledge_proposal {
+                nonce: prop_id,
+                src_id,
+                r_id,
+                call: Box::new(proposal.clone()),
+            });
+            assert_eq!(
+                vote.get_dispatch_info().weight,
+                max_vote_weight + call_weight
+            );
+
+            // The first vote doesn't execute the call, so its weight is refunded
+            // down to the actual relayer count
+            let post_info = Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone()),
+            )
+            .unwrap();
+            assert_eq!(post_info.actual_weight, Some(vote_weight));
+
+            // The second vote executes the call and is charged for it
+            let post_info = Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone()),
+            )
+            .unwrap();
+            assert_eq!(
+                post_info.actual_weight,
+                Some(vote_weight + call_weight)
+            );
+        })
+}
+
+#[test]
+fn committed_votes_are_free() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let vote = |relayer| {
+                Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone()),
+                )
+            };
+
+            let post_info = vote(RELAYER_A).unwrap();
+            assert_eq!(post_info.pays_fee, Pays::No);
+
+            // A vote that fails pays the fee
+            let err = vote(RELAYER_A).unwrap_err();
+            assert_eq!(err.post_info.pays_fee, Pays::Yes);
+        })
+}
+
+#[test]
+fn check_relayer_vote_extension() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let vote = mock::Call::Bridge(crate::Call::acknowledge_proposal {
+                nonce: 1,
+                src_id,
+                r_id,
+                call: Box::new(proposal.clone()),
+            });
+            let check = |who, call: &mock::Call| {
+                CheckRelayerVote::<Test>::new().validate(
+                    &who,
+                    call,
+                    &call.get_dispatch_info(),
+                    0,
+                )
+            };
+
+            assert_eq!(
+                check(DEPOSITOR, &vote),
+                Err(InvalidTransaction::Custom(
+                    ValidityError::NotRelayer.into()
+                )
+                .into())
+            );
+            assert!(check(RELAYER_A, &vote).is_ok());
+
+            // A relayer cannot vote twice
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            assert_eq!(
+                check(RELAYER_A, &vote),
+                Err(InvalidTransaction::Stale.into())
+            );
+            assert!(check(RELAYER_B, &vote).is_ok());
+
+            // A vote for another proposal with the nonce is let through, so
+            // the equivocation is recorded when it is dispatched
+            let conflicting = make_proposal(vec![11]);
+            let conflicting_vote =
+                mock::Call::Bridge(crate::Call::acknowledge_proposal {
+                    nonce: 1,
+                    src_id,
+                    r_id,
+                    call: Box::new(conflicting.clone()),
+                });
+            assert!(check(RELAYER_A, &conflicting_vote).is_ok());
+            let rejection = mock::Call::Bridge(crate::Call::reject_proposal {
+                nonce: 1,
+                src_id,
+                r_id,
+                call: Box::new(conflicting),
+            });
+            assert!(check(RELAYER_A, &rejection).is_ok());
+
+            // Calls other than votes are not checked
+            assert!(check(DEPOSITOR, &proposal).is_ok());
+        })
+}
+
+#[test]
+fn equivocation_passes_transaction_checks() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext().execute_with(|| {
+        let bond = 100;
+        RelayerBond::set(bond);
+        for relayer in [RELAYER_A, RELAYER_B, RELAYER_C] {
+            Balances::make_free_balance_be(&relayer, ENDOWED_BALANCE);
+            assert_ok!(Bridge::add_relayer(Origin::root(), relayer));
+        }
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            b"System.remark".to_vec()
+        ));
+        System::set_block_number(1);
+
+        let proposal = make_proposal(vec![10]);
+        let conflicting = make_proposal(vec![11]);
+        let slashed = RelayerSlashFraction::get() * bond;
+        let assert_slashed_and_removed = |relayer| {
+            assert_eq!(Bridge::equivocation(relayer, (src_id, 1)), Some(1));
+            assert!(!Bridge::is_relayer(&relayer));
+            assert_eq!(
+                Bridge::unbonding(relayer),
+                Some((bond - slashed, 1 + UnbondingPeriod::get()))
+            );
+        };
+
+        // A signed conflicting vote passes the pool checks and pays its fee
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let vote = mock::Call::Bridge(crate::Call::acknowledge_proposal {
+            nonce: 1,
+            src_id,
+            r_id,
+            call: Box::new(conflicting.clone()),
+        });
+        assert_ok!(CheckRelayerVote::<Test>::new().validate(
+            &RELAYER_A,
+            &vote,
+            &vote.get_dispatch_info(),
+            0,
+        ));
+        let err = vote.dispatch(Origin::signed(RELAYER_A)).unwrap_err();
+        assert_eq!(err.error, Error::<Test>::ConflictingVote.into());
+        assert_eq!(err.post_info.pays_fee, Pays::Yes);
+        assert_slashed_and_removed(RELAYER_A);
+
+        // So does an unsigned one
+        let payload = |prop: &mock::Call| VotePayload {
+            relayer: RELAYER_B,
+            src_id,
+            nonce: 1,
+            r_id,
+            prop_hash: BlakeTwo256::hash_of(prop),
+            in_favour: true,
+        };
+        let sign = |payload: &VotePayload<u64, _>| {
+            TestSignature(payload.relayer, Bridge::vote_message(payload))
+        };
+        let vote = payload(&proposal);
+        assert_ok!(Bridge::submit_vote_unsigned(
+            Origin::none(),
+            vote.clone(),
+            sign(&vote)
+        ));
+        let vote = payload(&conflicting);
+        assert_ok!(Bridge::validate_unsigned(
+            TransactionSource::External,
+            &crate::Call::submit_vote_unsigned {
+                payload: vote.clone(),
+                signature: sign(&vote),
+            },
+        ));
+        assert_err!(
+            Bridge::submit_vote_unsigned(
+                Origin::none(),
+                vote.clone(),
+                sign(&vote)
+            ),
+            Error::<Test>::ConflictingVote
+        );
+        assert_slashed_and_removed(RELAYER_B);
+    })
+}
+
+#[test]
+fn unsigned_relayer_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let payload = |relayer| VotePayload {
+                relayer,
+                src_id,
+                nonce: 1,
+                r_id,
+                prop_hash: BlakeTwo256::hash_of(&proposal),
+                in_favour: true,
+            };
+            let sign = |payload: &VotePayload<u64, _>| {
+                TestSignature(payload.relayer, Bridge::vote_message(payload))
+            };
+            let validate = |payload: &VotePayload<u64, _>, signature| {
+                Bridge::validate_unsigned(
+                    TransactionSource::External,
+                    &crate::Call::submit_vote_unsigned {
+                        payload: payload.clone(),
+                        signature,
+                    },
+                )
+            };
+
+            // The signature must be the relayer's, over the payload bound
+            // to this chain
+            let vote = payload(RELAYER_A);
+            assert_eq!(
+                validate(
+                    &vote,
+                    TestSignature(RELAYER_B, Bridge::vote_message(&vote))
+                ),
+                InvalidTransaction::BadProof.into()
+            );
+            assert_eq!(
+                validate(&vote, TestSignature(RELAYER_A, vote.encode())),
+                InvalidTransaction::BadProof.into()
+            );
+            let mut tampered = vote.clone();
+            tampered.in_favour = false;
+            assert_eq!(
+                validate(&tampered, sign(&vote)),
+                InvalidTransaction::BadProof.into()
+            );
+            let vote_of_depositor = payload(DEPOSITOR);
+            assert_eq!(
+                validate(&vote_of_depositor, sign(&vote_of_depositor)),
+                InvalidTransaction::Custom(ValidityError::NotRelayer.into())
+                    .into()
+            );
+
+            let valid = validate(&vote, sign(&vote)).unwrap();
+            assert_eq!(valid.priority, mock::UnsignedPriority::get());
+            assert_eq!(valid.longevity, mock::UnsignedLongevity::get());
+            assert_ok!(Bridge::submit_vote_unsigned(
+                Origin::none(),
+                vote.clone(),
+                sign(&vote)
+            ));
+
+            // A vote cannot be submitted twice
+            assert_eq!(
+                validate(&vote, sign(&vote)),
+                InvalidTransaction::Stale.into()
+            );
+
+            let vote = payload(RELAYER_B);
+            assert_ok!(validate(&vote, sign(&vote)));
+            assert_ok!(Bridge::submit_vote_unsigned(
+                Origin::none(),
+                vote.clone(),
+                sign(&vote)
+            ));
+            assert_eq!(
+                Bridge::awaiting_preimage(src_id, 1),
+                Some(BlakeTwo256::hash_of(&proposal))
+            );
+
+            // A vote that cannot be committed is not valid
+            let vote = VotePayload {
+                nonce: 2,
+                ..payload(RELAYER_C)
+            };
+            assert_ok!(Bridge::pause_bridge(Origin::root()));
+            assert_eq!(
+                validate(&vote, sign(&vote)),
+                InvalidTransaction::Custom(ValidityError::CannotCommit.into())
+                    .into()
+            );
+
+            // A vote rejected when dispatched cannot be submitted again
+            assert_err!(
+                Bridge::submit_vote_unsigned(
+                    Origin::none(),
+                    vote.clone(),
+                    sign(&vote)
+                ),
+                Error::<Test>::BridgePaused
+            );
+            assert!(Bridge::vote_rejected(
+                (src_id, 2),
+                BlakeTwo256::hash_of(&vote)
+            ));
+            assert_ok!(Bridge::unpause_bridge(Origin::root()));
+            assert_eq!(
+                validate(&vote, sign(&vote)),
+                InvalidTransaction::Stale.into()
+            );
+            assert_noop!(
+                Bridge::submit_vote_unsigned(
+                    Origin::none(),
+                    vote.clone(),
+                    sign(&vote)
+                ),
+                Error::<Test>::VoteRejected
+            );
+        })
+}
+
+#[test]
+fn submit_attested_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let valid_until = ProposalLifetime::get();
+            let attest = |relayer, nonce| {
+                let payload = VotePayload {
+                    relayer,
+                    src_id,
+                    nonce,
+                    r_id,
+                    prop_hash: BlakeTwo256::hash_of(&proposal),
+                    in_favour: true,
+                };
+                let message = Bridge::attestation_message(
+                    &payload,
+                    Bridge::relayer_epoch(),
+                    valid_until,
+                );
+                (relayer, TestSignature(relayer, message))
+            };
+            let submit_until =
+                |nonce,
+                 valid_until,
+                 attestations: Vec<(u64, TestSignature)>| {
+                    Bridge::submit_attested_proposal(
+                        Origin::signed(DEPOSITOR),
+                        nonce,
+                        src_id,
+                        r_id,
+                        Box::new(proposal.clone()),
+                        valid_until,
+                        AttestationsOf::<Test>::try_from(attestations).unwrap(),
+                    )
+                };
+            let submit = |nonce, attestations| {
+                submit_until(nonce, valid_until, attestations)
+            };
+
+            // Only valid signatures of distinct relayers count
+            let forged = (RELAYER_B, TestSignature(RELAYER_A, vec![]));
+            let other_nonce = attest(RELAYER_C, 2);
+            assert_noop!(
+                submit(
+                    1,
+                    vec![
+                        attest(RELAYER_A, 1),
+                        attest(RELAYER_A, 1),
+                        attest(DEPOSITOR, 1),
+                        forged,
+                        other_nonce,
+                    ]
+                ),
+                Error::<Test>::InsufficientAttestations
+            );
+
+            assert_ok!(submit(
+                1,
+                vec![attest(RELAYER_A, 1), attest(RELAYER_B, 1)]
+            ));
+            let votes =
+                Bridge::get_votes(src_id, (1, proposal.clone())).unwrap();
+            assert_eq!(votes.votes_for.to_vec(), vec![RELAYER_A, RELAYER_B]);
+            assert_eq!(votes.status, ProposalStatus::Approved);
+            assert!(Bridge::nonce_executed(src_id, 1));
+            assert_eq!(
+                Bridge::pending_rewards(RELAYER_A),
+                RelayerReward::get()
+            );
+            assert_events(vec![
+                mock::Event::Bridge(crate::Event::<Test>::ProposalApproved(
+                    src_id, 1,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalSucceeded(
+                    src_id, 1,
+                )),
+            ]);
+
+            assert_noop!(
+                submit(1, vec![attest(RELAYER_A, 1), attest(RELAYER_B, 1)]),
+                Error::<Test>::NonceAlreadyExecuted
+            );
+
+            // A nonce being voted on cannot be attested
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                2,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            assert_noop!(
+                submit(2, vec![attest(RELAYER_B, 2), attest(RELAYER_C, 2)]),
+                Error::<Test>::NonceInUse
+            );
+
+            // Attestations cannot be replayed once the relayer set changes
+            let attestations = vec![attest(RELAYER_A, 3), attest(RELAYER_B, 3)];
+            assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_C));
+            assert_noop!(
+                submit(3, attestations),
+                Error::<Test>::InsufficientAttestations
+            );
+
+            // Nor once they expire, and they cannot be valid for longer than
+            // a proposal lifetime
+            let attestations = vec![attest(RELAYER_A, 3), attest(RELAYER_B, 3)];
+            System::set_block_number(valid_until + 1);
+            assert_noop!(
+                submit(3, attestations.clone()),
+                Error::<Test>::InvalidAttestationPeriod
+            );
+            let too_late = valid_until + 2 + ProposalLifetime::get();
+            assert_noop!(
+                submit_until(3, too_late, attestations),
+                Error::<Test>::InvalidAttestationPeriod
+            );
+        })
+}
+
+#[test]
+fn acknowledge_proposals_batch() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let mismatched =
+                mock::Call::System(frame_system::Call::remark_with_event {
+                    remark: vec![11],
+                });
+            let batch = |items: Vec<(u64, &mock::Call)>| {
+                let items: Vec<_> = items
+                    .into_iter()
+                    .map(|(nonce, call)| {
+                        (nonce, src_id, r_id, Box::new(call.clone()))
+                    })
+                    .collect();
+                ProposalBatchOf::<Test>::try_from(items).unwrap()
+            };
+
+            // The weight covers the votes and the proposed calls
+            let proposals =
+                batch(vec![(1, &proposal), (2, &mismatched), (3, &proposal)]);
+            let votes_weight = 3 * <() as WeightInfo>::acknowledge_proposal(
+                MaxRelayers::get(),
+            );
+            let vote =
+                mock::Call::Bridge(crate::Call::acknowledge_proposals_batch {
+                    proposals: proposals.clone(),
+                });
+            assert_eq!(
+                vote.get_dispatch_info().weight,
+                votes_weight
+                    + 2 * proposal.get_dispatch_info().weight
+                    + mismatched.get_dispatch_info().weight
+            );
+
+            assert_noop!(
+                Bridge::acknowledge_proposals_batch(
+                    Origin::signed(DEPOSITOR),
+                    proposals.clone()
+                ),
+                Error::<Test>::MustBeRelayer
+            );
+            assert_noop!(
+                Bridge::acknowledge_proposals_batch(
+                    Origin::signed(RELAYER_A),
+                    batch(vec![])
+                ),
+                Error::<Test>::EmptyBatch
+            );
+
+            // A failing vote doesn't abort the batch
+            assert_ok!(Bridge::acknowledge_proposals_batch(
+                Origin::signed(RELAYER_A),
+                proposals
+            ));
+            assert_events(vec![
+                mock::Event::Bridge(crate::Event::<Test>::VoteFor(
+                    src_id, 1, RELAYER_A,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::BatchVoteCompleted(
+                    0,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::BatchVoteFailed(
+                    1,
+                    Error::<Test>::CallDoesNotMatchResource.into(),
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::VoteFor(
+                    src_id, 3, RELAYER_A,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::BatchVoteCompleted(
+                    2,
+                )),
+            ]);
+            assert_eq!(Bridge::get_votes(src_id, (2, mismatched)), None);
+
+            // Votes reaching the threshold execute their proposal
+            assert_ok!(Bridge::acknowledge_proposals_batch(
+                Origin::signed(RELAYER_B),
+                batch(vec![(1, &proposal), (1, &proposal)])
+            ));
+            assert_events(vec![
+                mock::Event::Bridge(crate::Event::<Test>::VoteFor(
+                    src_id, 1, RELAYER_B,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalApproved(
+                    src_id, 1,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalSucceeded(
+                    src_id, 1,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::BatchVoteCompleted(
+                    0,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::BatchVoteFailed(
+                    1,
+                    Error::<Test>::ProposalAlreadyComplete.into(),
+                )),
+            ]);
+            assert!(Bridge::nonce_executed(src_id, 1));
+            assert!(!Bridge::nonce_executed(src_id, 3));
+
+            // A conflicting vote fails, but its equivocation is kept. No
+            // vote is committed, so the batch pays its fee.
+            let result = Bridge::acknowledge_proposals_batch(
+                Origin::signed(RELAYER_A),
+                batch(vec![(3, &make_proposal(vec![11]))]),
+            );
+            assert_eq!(result.unwrap().pays_fee, Pays::Yes);
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::BatchVoteFailed(
+                    0,
+                    Error::<Test>::ConflictingVote.into(),
+                ),
+            )]);
+            assert_eq!(Bridge::equivocation(RELAYER_A, (src_id, 3)), Some(1));
+        })
+}
+
+#[test]
+fn failed_execution_keeps_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"heap_pages");
+
+    new_test_ext_initialized(src_id, r_id, b"System.set_heap_pages".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            // Requires root, so dispatching it from the bridge account fails
+            let proposal =
+                mock::Call::System(frame_system::Call::set_heap_pages {
+                    pages: 64,
+                });
+
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            // The final vote succeeds even though the execution fails
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+
+            let prop =
+                Bridge::get_votes(src_id, (prop_id, proposal.clone())).unwrap();
+            let expected = ProposalVotesOf::<Test> {
+                votes_for: vec![RELAYER_A, RELAYER_B].try_into().unwrap(),
+                votes_against: vec![].try_into().unwrap(),
+                status: ProposalStatus::ExecutionFailed(
+                    DispatchError::BadOrigin,
+                ),
+                expiry: ProposalLifetime::get() + 1,
+                epoch: 3,
+                threshold: TEST_THRESHOLD,
+                relayer_count: 3,
+                resolved_at: Some(1),
+                r_id,
+            };
+            assert_eq!(prop, expected);
+
+            assert_events(vec![
+                mock::Event::Bridge(crate::Event::<Test>::VoteFor(
+                    src_id, prop_id, RELAYER_B,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalApproved(
+                    src_id, prop_id,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalFailed(
+                    src_id, prop_id,
+                )),
+            ]);
+
+            // The proposal is complete and cannot be voted on again
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_C),
+                    prop_id,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<Test>::ProposalAlreadyComplete
+            );
+        })
+}
+
+#[test]
+fn proposed_call_must_match_resource() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let heap_pages =
+                mock::Call::System(frame_system::Call::set_heap_pages {
+                    pages: 64,
+                });
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(heap_pages.clone())
+                ),
+                Error::<Test>::CallDoesNotMatchResource
+            );
+            assert_noop!(
+                Bridge::reject_proposal(
+                    Origin::signed(RELAYER_A),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(heap_pages)
+                ),
+                Error::<Test>::CallDoesNotMatchResource
+            );
+
+            // The whole method name has to match
+            let other_id = derive_resource_id(src_id, b"remark_with_event");
+            assert_ok!(Bridge::set_resource(
+                Origin::root(),
+                other_id,
+                b"System.remark_with_event".to_vec()
+            ));
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    1,
+                    src_id,
+                    other_id,
+                    Box::new(make_proposal(vec![10]))
+                ),
+                Error::<Test>::CallDoesNotMatchResource
+            );
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                Box::new(make_proposal(vec![10]))
+            ));
+        })
+}
+
+#[test]
+fn filtered_proposal_is_refused() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"Balances.transfer".to_vec())
+        .execute_with(|| {
+            let proposal =
+                mock::Call::Balances(pallet_balances::Call::transfer {
+                    dest: RELAYER_A,
+                    value: 10,
+                });
+            assert_noop!(
+                Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(proposal)
+                ),
+                Error::<Test>::ProposalFiltered
+            );
+        })
+}
+
+#[test]
+fn nonce_executes_once() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let first = make_proposal(vec![10]);
+            let second = make_proposal(vec![11]);
+            let acknowledge = |relayer, nonce, proposal: &mock::Call| {
+                Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    nonce,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone()),
+                )
+            };
+            let reject = |relayer, nonce, proposal: &mock::Call| {
+                Bridge::reject_proposal(
+                    Origin::signed(relayer),
+                    nonce,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone()),
+                )
+            };
+
+            // While a proposal is open, its nonce cannot be used by another
+            assert_ok!(acknowledge(RELAYER_A, 1, &first));
+            assert_noop!(
+                acknowledge(RELAYER_B, 1, &second),
+                Error::<Test>::NonceInUse
+            );
+            assert_noop!(
+                reject(RELAYER_B, 1, &second),
+                Error::<Test>::NonceInUse
+            );
+
+            // Once executed, the nonce cannot be used again
+            assert_ok!(acknowledge(RELAYER_B, 1, &first));
+            assert!(Bridge::nonce_executed(src_id, 1));
+            assert_eq!(Bridge::in_flight_proposal(src_id, 1), None);
+            assert_noop!(
+                acknowledge(RELAYER_C, 1, &second),
+                Error::<Test>::NonceAlreadyExecuted
+            );
+
+            // A rejected proposal frees its nonce
+            assert_ok!(reject(RELAYER_A, 2, &first));
+            assert_ok!(reject(RELAYER_B, 2, &first));
+            assert_eq!(
+                Bridge::get_votes(src_id, (2, first.clone()))
+                    .unwrap()
+                    .status,
+                ProposalStatus::Rejected
+            );
+            assert_eq!(Bridge::in_flight_proposal(src_id, 2), None);
+            assert_ok!(acknowledge(RELAYER_A, 2, &second));
+            assert_ok!(acknowledge(RELAYER_B, 2, &second));
+            assert!(Bridge::nonce_executed(src_id, 2));
+
+            // Nonces are tracked per source chain
+            assert!(!Bridge::nonce_executed(src_id + 1, 1));
+        })
+}
+
+#[test]
+fn vote_on_proposal_hash() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let prop_hash = BlakeTwo256::hash_of(&proposal);
+            let acknowledge_hash = |relayer, nonce| {
+                Bridge::acknowledge_proposal_hash(
+                    Origin::signed(relayer),
+                    nonce,
+                    src_id,
+                    r_id,
+                    prop_hash,
+                )
+            };
+            let execute = |nonce, proposal: &mock::Call| {
+                Bridge::execute_proposal(
+                    Origin::signed(DEPOSITOR),
+                    nonce,
+                    src_id,
+                    Box::new(proposal.clone()),
+                )
+            };
+
+            assert_ok!(acknowledge_hash(RELAYER_A, 1));
+            assert_noop!(
+                execute(1, &proposal),
+                Error::<Test>::ProposalNotAwaitingPreimage
+            );
+
+            // Approved without the call, the proposal awaits it
+            assert_ok!(acknowledge_hash(RELAYER_B, 1));
+            assert_eq!(
+                Bridge::get_votes(src_id, (1, proposal.clone()))
+                    .unwrap()
+                    .status,
+                ProposalStatus::Approved
+            );
+            assert_eq!(Bridge::awaiting_preimage(src_id, 1), Some(prop_hash));
+            assert!(!Bridge::nonce_executed(src_id, 1));
+
+            // Anyone can supply the call, which must match the hash
+            assert_noop!(
+                execute(1, &make_proposal(vec![11])),
+                Error::<Test>::ProposalHashMismatch
+            );
+            assert_ok!(execute(1, &proposal));
+            assert!(Bridge::nonce_executed(src_id, 1));
+            assert_eq!(Bridge::awaiting_preimage(src_id, 1), None);
+            assert_events(vec![
+                mock::Event::Bridge(crate::Event::<Test>::VoteFor(
+                    src_id, 1, RELAYER_B,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalApproved(
+                    src_id, 1,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalSucceeded(
+                    src_id, 1,
+                )),
+            ]);
+
+            // A vote carrying the call executes the proposal right away
+            assert_ok!(acknowledge_hash(RELAYER_A, 2));
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                2,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            assert!(Bridge::nonce_executed(src_id, 2));
+            assert_eq!(Bridge::awaiting_preimage(src_id, 2), None);
+        })
+}
+
+#[test]
+fn hash_votes_bind_the_resource() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+    let other = derive_resource_id(src_id, b"remark_with_event");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            assert_ok!(Bridge::set_resource(
+                Origin::root(),
+                other,
+                b"System.remark_with_event".to_vec()
+            ));
+            let proposal = make_proposal(vec![10]);
+            let prop_hash = BlakeTwo256::hash_of(&proposal);
+            let acknowledge_hash = |relayer, nonce, r_id| {
+                Bridge::acknowledge_proposal_hash(
+                    Origin::signed(relayer),
+                    nonce,
+                    src_id,
+                    r_id,
+                    prop_hash,
+                )
+            };
+
+            // Every vote must be cast under the resource of the first
+            assert_ok!(acknowledge_hash(RELAYER_A, 1, r_id));
+            assert_noop!(
+                acknowledge_hash(RELAYER_B, 1, other),
+                Error::<Test>::ProposalResourceMismatch
+            );
+
+            // The call is checked against the resource voted on
+            assert_ok!(acknowledge_hash(RELAYER_A, 2, other));
+            assert_ok!(acknowledge_hash(RELAYER_B, 2, other));
+            assert_eq!(Bridge::awaiting_preimage(src_id, 2), Some(prop_hash));
+            assert_noop!(
+                Bridge::execute_proposal(
+                    Origin::signed(DEPOSITOR),
+                    2,
+                    src_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<Test>::CallDoesNotMatchResource
+            );
+        })
+}
+
+#[test]
+fn disabled_source_chain_blocks_execution() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let prop_hash = BlakeTwo256::hash_of(&proposal);
+            for relayer in [RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal_hash(
+                    Origin::signed(relayer),
+                    1,
+                    src_id,
+                    r_id,
+                    prop_hash,
+                ));
+            }
+            let execute = || {
+                Bridge::execute_proposal(
+                    Origin::signed(DEPOSITOR),
+                    1,
+                    src_id,
+                    Box::new(proposal.clone()),
+                )
+            };
+
+            assert_ok!(Bridge::disable_chain(Origin::root(), src_id));
+            assert_noop!(execute(), Error::<Test>::ChainNotWhitelisted);
+            assert_noop!(
+                Bridge::eval_vote_state(
+                    Origin::signed(DEPOSITOR),
+                    1,
+                    src_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<Test>::ChainNotWhitelisted
+            );
+
+            assert_ok!(Bridge::enable_chain(Origin::root(), src_id));
+            assert_ok!(execute());
+            assert!(Bridge::nonce_executed(src_id, 1));
+        })
+}
+
+#[test]
+fn inbound_cap_parks_proposals() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"release");
+
+    new_test_ext_initialized(src_id, r_id, b"Handler.release".to_vec())
+        .execute_with(|| {
+            let limit = InboundLimit {
+                max_per_window: 100.into(),
+                window: 10,
+            };
+            // The resource reported by the handler is ignored, the cap of
+            // the resource the proposal is made under applies
+            let other = derive_resource_id(src_id, b"other");
+            let release = |amount: u64| {
+                mock::Call::Handler(handler::Call::release {
+                    _r_id: other,
+                    amount,
+                })
+            };
+            let approve = |nonce, proposal: &mock::Call| {
+                for relayer in [RELAYER_A, RELAYER_B] {
+                    assert_ok!(Bridge::acknowledge_proposal(
+                        Origin::signed(relayer),
+                        nonce,
+                        src_id,
+                        r_id,
+                        Box::new(proposal.clone())
+                    ));
+                }
+            };
+
+            System::set_block_number(1);
+            assert_noop!(
+                Bridge::set_inbound_limit(
+                    Origin::signed(RELAYER_A),
+                    src_id,
+                    r_id,
+                    Some(limit.clone())
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Bridge::set_inbound_limit(
+                Origin::root(),
+                src_id,
+                r_id,
+                Some(limit.clone())
+            ));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::InboundLimitChanged(
+                    src_id,
+                    r_id,
+                    Some(limit),
+                ),
+            )]);
+
+            // Within the cap the proposal executes
+            approve(1, &release(80));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalSucceeded(src_id, 1),
+            )]);
+            assert_eq!(
+                Bridge::inbound_usage(src_id, r_id),
+                Some(TransferUsage {
+                    volume: 80.into(),
+                    deposits: 1,
+                    updated_at: 1,
+                })
+            );
+
+            // Beyond the cap it is parked, keeping the approval
+            let parked = release(50);
+            approve(2, &parked);
+            assert_events(vec![
+                mock::Event::Bridge(crate::Event::<Test>::ProposalApproved(
+                    src_id, 2,
+                )),
+                mock::Event::Bridge(crate::Event::<Test>::ProposalParked(
+                    src_id, 2,
+                )),
+            ]);
+            assert_eq!(
+                Bridge::pending_proposal(
+                    src_id,
+                    (2, BlakeTwo256::hash_of(&parked))
+                ),
+                Some((r_id, 1 + PendingLifetime::get()))
+            );
+            assert_eq!(
+                Bridge::get_votes(src_id, (2, parked.clone()))
+                    .unwrap()
+                    .status,
+                ProposalStatus::Approved
+            );
+            assert_eq!(
+                Bridge::inbound_usage(src_id, r_id).unwrap().volume,
+                80.into()
+            );
+            assert_noop!(
+                Bridge::retry_pending(
+                    Origin::signed(DEPOSITOR),
+                    2,
+                    src_id,
+                    Box::new(parked.clone())
+                ),
+                Error::<Test>::InboundCapExceeded
+            );
+
+            // Once the window has drained enough, anyone can retry it
+            System::set_block_number(5);
+            // Not while the source chain is disabled
+            assert_ok!(Bridge::disable_chain(Origin::root(), src_id));
+            assert_noop!(
+                Bridge::retry_pending(
+                    Origin::signed(DEPOSITOR),
+                    2,
+                    src_id,
+                    Box::new(parked.clone())
+                ),
+                Error::<Test>::ChainNotWhitelisted
+            );
+            assert_ok!(Bridge::enable_chain(Origin::root(), src_id));
+            assert_ok!(Bridge::retry_pending(
+                Origin::signed(DEPOSITOR),
+                2,
+                src_id,
+                Box::new(parked.clone())
+            ));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalSucceeded(src_id, 2),
+            )]);
+            assert_eq!(
+                Bridge::pending_proposal(
+                    src_id,
+                    (2, BlakeTwo256::hash_of(&parked))
+                ),
+                None
+            );
+            assert_eq!(
+                Bridge::inbound_usage(src_id, r_id).unwrap().volume,
+                90.into()
+            );
+            assert_noop!(
+                Bridge::retry_pending(
+                    Origin::signed(DEPOSITOR),
+                    2,
+                    src_id,
+                    Box::new(parked)
+                ),
+                Error::<Test>::ProposalNotPending
+            );
+
+            // The admin can release a parked proposal regardless of the cap
+            let large = release(200);
+            approve(3, &large);
+            assert_eq!(
+                Bridge::pending_proposal(
+                    src_id,
+                    (3, BlakeTwo256::hash_of(&large))
+                ),
+                Some((r_id, 5 + PendingLifetime::get()))
+            );
+            assert_noop!(
+                Bridge::approve_pending(
+                    Origin::signed(RELAYER_A),
+                    3,
+                    src_id,
+                    Box::new(large.clone())
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Bridge::approve_pending(
+                Origin::root(),
+                3,
+                src_id,
+                Box::new(large.clone())
+            ));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalSucceeded(src_id, 3),
+            )]);
+            assert_eq!(
+                Bridge::pending_proposal(
+                    src_id,
+                    (3, BlakeTwo256::hash_of(&large))
+                ),
+                None
+            );
+            assert_eq!(
+                Bridge::inbound_usage(src_id, r_id).unwrap().volume,
+                290.into()
+            );
+            assert_eq!(Bridge::inbound_usage(src_id, other), None);
+        })
+}
+
+#[test]
+fn parked_proposal_expires() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"release");
+
+    new_test_ext_initialized(src_id, r_id, b"Handler.release".to_vec())
+        .execute_with(|| {
+            let limit = InboundLimit {
+                max_per_window: 100.into(),
+                window: 10,
+            };
+            assert_ok!(Bridge::set_inbound_limit(
+                Origin::root(),
+                src_id,
+                r_id,
+                Some(limit)
+            ));
+            let parked = mock::Call::Handler(handler::Call::release {
+                _r_id: r_id,
+                amount: 200,
+            });
+            let prop_hash = BlakeTwo256::hash_of(&parked);
+            for relayer in [RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    1,
+                    src_id,
+                    r_id,
+                    Box::new(parked.clone())
+                ));
+            }
+            let expiry = 1 + PendingLifetime::get();
+            assert_eq!(
+                Bridge::pending_proposal(src_id, (1, prop_hash)),
+                Some((r_id, expiry))
+            );
+
+            // Kept past the expiry and retention of its votes while parked
+            Bridge::on_idle(expiry - 1, Weight::max_value());
+            assert!(Bridge::pending_proposal(src_id, (1, prop_hash)).is_some());
+            assert!(Bridge::get_votes(src_id, (1, parked.clone())).is_some());
+
+            // Once expired it can no longer be released
+            System::set_block_number(expiry);
+            assert_noop!(
+                Bridge::approve_pending(
+                    Origin::root(),
+                    1,
+                    src_id,
+                    Box::new(parked.clone())
+                ),
+                Error::<Test>::ProposalExpired
+            );
+
+            // Pruning removes it and frees its nonce
+            Bridge::on_idle(expiry, Weight::max_value());
+            assert_eq!(Bridge::pending_proposal(src_id, (1, prop_hash)), None);
+            assert_eq!(Bridge::get_votes(src_id, (1, parked)), None);
+            assert_eq!(Bridge::in_flight_proposal(src_id, 1), None);
+            assert!(!Bridge::nonce_executed(src_id, 1));
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalExpired(src_id, 1),
+            )]);
+        })
+}
+
+#[test]
+fn expired_proposal_is_pruned() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            let proposal = make_proposal(vec![10]);
+            let expiry = ProposalLifetime::get() + 1;
+
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+
+            // Not expired yet
+            Bridge::on_idle(expiry - 1, Weight::max_value());
+            assert!(Bridge::get_votes(src_id, (prop_id, proposal.clone()))
+                .is_some());
+
+            Bridge::on_idle(expiry, Weight::max_value());
+            assert!(Bridge::get_votes(src_id, (prop_id, proposal.clone()))
+                .is_none());
+
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalExpired(src_id, prop_id),
+            )]);
+        })
+}
+
+#[test]
+fn proposal_awaiting_preimage_is_pruned() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            let proposal = make_proposal(vec![10]);
+            let prop_hash = BlakeTwo256::hash_of(&proposal);
+            let expiry = ProposalLifetime::get() + 1;
+
+            for relayer in [RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal_hash(
+                    Origin::signed(relayer),
+                    prop_id,
+                    src_id,
+                    r_id,
+                    prop_hash,
+                ));
+            }
+            assert_eq!(
+                Bridge::awaiting_preimage(src_id, prop_id),
+                Some(prop_hash)
+            );
+
+            // The call was never supplied, so the nonce is released
+            Bridge::on_idle(expiry, Weight::max_value());
+            assert!(Bridge::get_votes(src_id, (prop_id, proposal.clone()))
+                .is_none());
+            assert_eq!(Bridge::awaiting_preimage(src_id, prop_id), None);
+            assert_eq!(Bridge::in_flight_proposal(src_id, prop_id), None);
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalExpired(src_id, prop_id),
+            )]);
+            assert_noop!(
+                Bridge::execute_proposal(
+                    Origin::signed(DEPOSITOR),
+                    prop_id,
+                    src_id,
+                    Box::new(proposal.clone())
+                ),
+                Error::<Test>::ProposalNotAwaitingPreimage
+            );
+
+            // The proposal can be made again with its call
+            for relayer in [RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(relayer),
+                    prop_id,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ));
+            }
+            assert!(Bridge::nonce_executed(src_id, prop_id));
+        })
+}
+
+#[test]
+fn pruning_stops_at_weight_limit_and_resumes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let expiry = ProposalLifetime::get() + 1;
+            for prop_id in 1..=3 {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    prop_id,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ));
+            }
+
+            // Enough weight for two of the three expired proposals
+            let limit = <() as WeightInfo>::on_idle(2)
+                + RocksDbWeight::get().reads_writes(1, 1);
+            assert_eq!(Bridge::on_idle(expiry, limit), limit);
+            let remaining: Vec<_> = (1..=3)
+                .filter(|prop_id| {
+                    Bridge::get_votes(src_id, (*prop_id, proposal.clone()))
+                        .is_some()
+                })
+                .collect();
+            assert_eq!(remaining, vec![1]);
+            assert_eq!(PruneQueue::<Test>::get(expiry).len(), 1);
+
+            // Not even enough weight to read the cursor
+            assert_eq!(Bridge::on_idle(expiry + 1, 0), 0);
+            assert!(Bridge::get_votes(src_id, (1, proposal.clone())).is_some());
+
+            // The next block resumes where pruning stopped
+            Bridge::on_idle(expiry + 1, Weight::max_value());
+            assert!(Bridge::get_votes(src_id, (1, proposal.clone())).is_none());
+            assert!(PruneQueue::<Test>::get(expiry).is_empty());
+        })
+}
+
+#[test]
+fn full_prune_queue_spills_into_next_block() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let proposal = make_proposal(vec![10]);
+            let expiry = ProposalLifetime::get() + 1;
+            let max = MaxPrunePerBlock::get() as u64;
+            for prop_id in 1..=max + 1 {
+                assert_ok!(Bridge::acknowledge_proposal(
+                    Origin::signed(RELAYER_A),
+                    prop_id,
+                    src_id,
+                    r_id,
+                    Box::new(proposal.clone())
+                ));
+            }
+
+            assert_eq!(PruneQueue::<Test>::get(expiry).len() as u64, max);
+            assert_eq!(PruneQueue::<Test>::get(expiry + 1).len(), 1);
+
+            Bridge::on_idle(expiry + 1, Weight::max_value());
+            for prop_id in 1..=max + 1 {
+                assert!(Bridge::get_votes(src_id, (prop_id, proposal.clone()))
+                    .is_none());
+            }
+        })
+}
+
+#[test]
+fn complete_proposal_is_pruned_after_retention() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec())
+        .execute_with(|| {
+            let prop_id = 1;
+            let proposal = make_proposal(vec![10]);
+            let expiry = ProposalLifetime::get() + 1;
+            let resolved_at = expiry - 2;
+
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+            System::set_block_number(resolved_at);
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+
+            // Kept past its expiry for the retention period since it was
+            // resolved
+            Bridge::on_idle(expiry, Weight::max_value());
+            let prop =
+                Bridge::get_votes(src_id, (prop_id, proposal.clone())).unwrap();
+            assert_eq!(prop.status, ProposalStatus::Approved);
+            assert_eq!(prop.resolved_at, Some(resolved_at));
+
+            Bridge::on_idle(
+                resolved_at + ProposalRetention::get() - 1,
+                Weight::max_value(),
+            );
+            assert!(Bridge::get_votes(src_id, (prop_id, proposal.clone()))
+                .is_some());
+
+            Bridge::on_idle(
+                resolved_at + ProposalRetention::get(),
+                Weight::max_value(),
+            );
+            assert!(Bridge::get_votes(src_id, (prop_id, proposal.clone()))
+                .is_none());
+
+            assert_events(vec![mock::Event::Bridge(
+                crate::Event::<Test>::ProposalSucceeded(src_id, prop_id),
+            )]);
+        })
+}
@@ -1,9 +1,15 @@
 #![deny(warnings)]
 
 use codec::{Decode, Encode};
-use frame_support::{inherent::*, pallet_prelude::*};
+use frame_support::{
+    inherent::*,
+    pallet_prelude::*,
+    sp_runtime::traits::{AtLeast32BitUnsigned, SaturatedConversion},
+    CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use scale_info::TypeInfo;
-use sp_std::prelude::*;
+use sp_core::U256;
+use sp_std::{fmt::Debug, prelude::*};
 
 pub type ChainId = u8;
 pub type DepositNonce = u64;
@@ -14,49 +20,160 @@ pub enum ProposalStatus {
     Initiated,
     Approved,
     Rejected,
+    /// The proposal was approved, but dispatching it failed
+    ExecutionFailed(DispatchError),
+}
+
+impl MaxEncodedLen for ProposalStatus {
+    fn max_encoded_len() -> usize {
+        // The status variant, then the error variant followed by at most the
+        // pallet index and error index of a module error. The messages of
+        // errors are not encoded.
+        1 + 1 + 2
+    }
+}
+
+/// Kind of a bridged chain
+#[derive(
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum ChainKind {
+    Evm,
+    Substrate,
+    Other,
+}
+
+/// Metadata of a whitelisted chain, with a name of at most `MaxNameLen`
+/// bytes.
+#[derive(
+    PartialEqNoBound,
+    EqNoBound,
+    CloneNoBound,
+    Encode,
+    Decode,
+    RuntimeDebugNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxNameLen))]
+#[codec(mel_bound())]
+pub struct ChainInfo<MaxNameLen: Get<u32>> {
+    /// Nonce of the last outbound transfer to the chain
+    pub nonce: DepositNonce,
+    pub kind: ChainKind,
+    /// Length recipients on the chain must have, if it is fixed
+    pub recipient_len: Option<u32>,
+    /// Whether transfers and proposals are accepted
+    pub enabled: bool,
+    pub name: BoundedVec<u8, MaxNameLen>,
+}
+
+impl<MaxNameLen: Get<u32>> ChainInfo<MaxNameLen> {
+    /// Enabled chain of unknown kind, starting from `nonce`
+    pub fn new(nonce: DepositNonce) -> Self {
+        Self {
+            nonce,
+            kind: ChainKind::Other,
+            recipient_len: None,
+            enabled: true,
+            name: BoundedVec::default(),
+        }
+    }
 }
 
 //TODO: Why do I need to use TypeInfo now? when the old version or PR#94 dont have to use it?
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct ProposalVotes<AccountId, BlockNumber> {
-    pub votes_for: Vec<AccountId>,
-    pub votes_against: Vec<AccountId>,
+/// Votes of a proposal, with at most `MaxVotes` votes on either side.
+#[derive(
+    PartialEqNoBound,
+    EqNoBound,
+    CloneNoBound,
+    Encode,
+    Decode,
+    RuntimeDebugNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxVotes))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen))]
+pub struct ProposalVotes<AccountId, BlockNumber, MaxVotes>
+where
+    AccountId: Clone + Eq + Debug,
+    BlockNumber: Clone + Eq + Debug,
+    MaxVotes: Get<u32>,
+{
+    pub votes_for: BoundedVec<AccountId, MaxVotes>,
+    pub votes_against: BoundedVec<AccountId, MaxVotes>,
     pub status: ProposalStatus,
     pub expiry: BlockNumber,
+    /// Relayer-set epoch the proposal was created in. Only votes of
+    /// relayers that joined in or before this epoch count.
+    pub epoch: u32,
+    /// Number of votes required, as in force when the proposal was created
+    pub threshold: u32,
+    /// Number of relayers when the proposal was created
+    pub relayer_count: u32,
+    /// Block the proposal was approved or rejected at
+    pub resolved_at: Option<BlockNumber>,
+    /// Resource the proposal was made under, which every vote must match.
+    /// Zeroed for proposals made before it was recorded.
+    pub r_id: ResourceId,
 }
 
-impl<AccountId, BlockNumber> Default for ProposalVotes<AccountId, BlockNumber>
+impl<AccountId, BlockNumber, MaxVotes> Default
+    for ProposalVotes<AccountId, BlockNumber, MaxVotes>
 where
-    BlockNumber: Default,
+    AccountId: Clone + Eq + Debug,
+    BlockNumber: Clone + Eq + Debug + Default,
+    MaxVotes: Get<u32>,
 {
     fn default() -> Self {
         Self {
-            votes_for: vec![],
-            votes_against: vec![],
+            votes_for: BoundedVec::default(),
+            votes_against: BoundedVec::default(),
             status: ProposalStatus::Initiated,
             expiry: BlockNumber::default(),
+            epoch: 0,
+            threshold: 0,
+            relayer_count: 0,
+            resolved_at: None,
+            r_id: [0; 32],
         }
     }
 }
 
-impl<AccountId, BlockNumber> ProposalVotes<AccountId, BlockNumber>
+impl<AccountId, BlockNumber, MaxVotes>
+    ProposalVotes<AccountId, BlockNumber, MaxVotes>
 where
-    AccountId: PartialEq,
-    BlockNumber: PartialOrd,
+    AccountId: Clone + Eq + Debug,
+    BlockNumber: Clone + Eq + Debug + PartialOrd,
+    MaxVotes: Get<u32>,
 {
-    /// Attempts to mark the proposal as approve or rejected.
-    /// Returns true if the status changes from active.
-    /// TODO: use saturating_add
+    /// Attempts to mark the proposal as approve or rejected, against the
+    /// threshold and relayer count recorded when it was created. Only the
+    /// votes of relayers for which `is_member` holds count. Returns the
+    /// resulting status, which is `Initiated` while neither side has
+    /// enough votes.
     pub(crate) fn try_to_complete(
         &mut self,
-        threshold: u32,
-        total: u32,
+        is_member: impl Fn(&AccountId) -> bool,
     ) -> ProposalStatus {
-        if self.votes_for.len() >= threshold as usize {
+        let count = |votes: &BoundedVec<AccountId, MaxVotes>| {
+            votes.iter().filter(|who| is_member(who)).count() as u32
+        };
+        let (threshold, total) = (self.threshold, self.relayer_count);
+        if count(&self.votes_for) >= threshold {
             self.status = ProposalStatus::Approved;
             ProposalStatus::Approved
         } else if total >= threshold
-            && self.votes_against.len() as u32 + threshold > total
+            && count(&self.votes_against).saturating_add(threshold) > total
         {
             self.status = ProposalStatus::Rejected;
             ProposalStatus::Rejected
@@ -65,7 +182,8 @@ where
         }
     }
 
-    /// Returns true if the proposal has been rejected or approved, otherwise false.
+    /// Returns true if the proposal has been rejected, approved or its execution
+    /// failed, otherwise false.
     pub(crate) fn is_complete(&self) -> bool {
         self.status != ProposalStatus::Initiated
     }
@@ -80,3 +198,103 @@ where
         self.expiry <= now
     }
 }
+
+/// Limits on the outbound transfers of a resource. A limit of `None` does not
+/// apply. Non-fungible transfers count one per token towards the amount
+/// limits, generic transfers only count towards the deposits per block.
+#[derive(
+    PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct TransferLimit<BlockNumber> {
+    /// Maximum amount of a single transfer
+    pub max_per_transfer: Option<U256>,
+    /// Maximum amount transferred within `window` blocks
+    pub max_per_window: Option<U256>,
+    /// Number of blocks over which `max_per_window` applies
+    pub window: BlockNumber,
+    /// Maximum number of deposits in a single block
+    pub max_deposits_per_block: Option<u32>,
+}
+
+/// Outbound transfer usage of a resource.
+///
+/// The volume drains linearly at `max_per_window / window` per block, so the
+/// capacity used by a transfer frees up gradually over the window instead of
+/// all at once.
+#[derive(
+    PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct TransferUsage<BlockNumber> {
+    /// Volume counted against `max_per_window` as of block `updated_at`
+    pub volume: U256,
+    /// Number of deposits made in block `updated_at`
+    pub deposits: u32,
+    /// Block the usage was last updated at
+    pub updated_at: BlockNumber,
+}
+
+impl<BlockNumber> TransferUsage<BlockNumber>
+where
+    BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+    /// Usage of a resource nothing has been transferred of yet
+    pub fn new(now: BlockNumber) -> Self {
+        Self {
+            volume: U256::zero(),
+            deposits: 0,
+            updated_at: now,
+        }
+    }
+
+    /// Brings the usage forward to block `now`. The deposits restart from
+    /// zero and the volume drains for the blocks elapsed since the last
+    /// update. Without a volume cap, or with an empty window, the volume
+    /// drains completely.
+    pub fn advance(
+        &mut self,
+        now: BlockNumber,
+        max_per_window: Option<U256>,
+        window: BlockNumber,
+    ) {
+        if self.updated_at == now {
+            return;
+        }
+        let drained = match max_per_window {
+            Some(max) if !window.is_zero() => {
+                let elapsed = now.saturating_sub(self.updated_at);
+                let elapsed = U256::from(elapsed.saturated_into::<u128>());
+                let window = U256::from(window.saturated_into::<u128>());
+                max.saturating_mul(elapsed) / window
+            }
+            _ => self.volume,
+        };
+        self.volume = self.volume.saturating_sub(drained);
+        self.deposits = 0;
+        self.updated_at = now;
+    }
+}
+
+/// Cap on the value released by inbound proposals of a resource from a
+/// source chain
+#[derive(
+    PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct InboundLimit<BlockNumber> {
+    /// Maximum value released within `window` blocks
+    pub max_per_window: U256,
+    /// Number of blocks over which `max_per_window` applies
+    pub window: BlockNumber,
+}
+
+/// Vote of a relayer on the proposal with call hash `prop_hash`, signed by
+/// the relayer so anyone can submit it as an unsigned transaction
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct VotePayload<AccountId, Hash> {
+    pub relayer: AccountId,
+    pub src_id: ChainId,
+    pub nonce: DepositNonce,
+    pub r_id: ResourceId,
+    pub prop_hash: Hash,
+    /// Whether the vote is in favour of the proposal
+    pub in_favour: bool,
+}
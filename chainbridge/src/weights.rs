@@ -0,0 +1,567 @@
+//! Weights for chainbridge
+//!
+//! PLACEHOLDER VALUES: these weights are hand-written estimates, not the
+//! output of the benchmark CLI. They follow its format so the file can be
+//! replaced as is by running the chainbridge benchmarks of the node template
+//! on reference hardware with `--output=./chainbridge/src/weights.rs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for chainbridge.
+pub trait WeightInfo {
+	fn set_threshold() -> Weight;
+	fn set_threshold_ratio() -> Weight;
+	fn set_resource() -> Weight;
+	fn remove_resource() -> Weight;
+	fn whitelist_chain() -> Weight;
+	fn register_chain() -> Weight;
+	fn disable_chain() -> Weight;
+	fn enable_chain() -> Weight;
+	fn add_relayer() -> Weight;
+	fn remove_relayer() -> Weight;
+	fn set_fee() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn set_transfer_limit() -> Weight;
+	fn set_inbound_limit() -> Weight;
+	fn retry_pending() -> Weight;
+	fn approve_pending() -> Weight;
+	fn pause_bridge() -> Weight;
+	fn unpause_bridge() -> Weight;
+	fn pause_chain() -> Weight;
+	fn unpause_chain() -> Weight;
+	fn acknowledge_proposal(v: u32, ) -> Weight;
+	fn reject_proposal(v: u32, ) -> Weight;
+	fn eval_vote_state(v: u32, ) -> Weight;
+	fn acknowledge_proposal_hash(v: u32, ) -> Weight;
+	fn reject_proposal_hash(v: u32, ) -> Weight;
+	fn execute_proposal() -> Weight;
+	fn submit_attested_proposal(a: u32, ) -> Weight;
+	fn submit_vote_unsigned(v: u32, ) -> Weight;
+	fn on_idle(n: u32, ) -> Weight;
+}
+
+/// Weights for chainbridge using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge RelayerThreshold (r:0 w:1)
+	// Storage: Chainbridge RelayerThresholdRatio (r:0 w:1)
+	fn set_threshold() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Chainbridge RelayerThresholdRatio (r:0 w:1)
+	fn set_threshold_ratio() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Resources (r:0 w:1)
+	fn set_resource() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Resources (r:0 w:1)
+	fn remove_resource() -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Chains (r:1 w:1)
+	fn whitelist_chain() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Chains (r:1 w:1)
+	fn register_chain() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Chains (r:1 w:1)
+	fn disable_chain() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Chains (r:1 w:1)
+	fn enable_chain() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Relayers (r:1 w:1)
+	// Storage: Chainbridge RelayerCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Chainbridge RelayerBonds (r:0 w:1)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:1)
+	// Storage: Chainbridge RelayerSince (r:0 w:1)
+	fn add_relayer() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Chainbridge Relayers (r:1 w:1)
+	// Storage: Chainbridge RelayerCount (r:1 w:1)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerBonds (r:1 w:1)
+	// Storage: Chainbridge Unbonding (r:1 w:1)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:1)
+	// Storage: Chainbridge RelayerSince (r:0 w:1)
+	fn remove_relayer() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Chainbridge BridgeFees (r:0 w:1)
+	fn set_fee() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge PendingRewards (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_rewards() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Chainbridge Unbonding (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Chainbridge TransferLimits (r:0 w:1)
+	// Storage: Chainbridge TransferUsages (r:0 w:1)
+	fn set_transfer_limit() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Chainbridge InboundLimits (r:0 w:1)
+	// Storage: Chainbridge InboundUsages (r:0 w:1)
+	fn set_inbound_limit() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge PendingProposals (r:1 w:1)
+	// Storage: Chainbridge InboundContext (r:0 w:2)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge InFlightProposals (r:0 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:0 w:1)
+	fn retry_pending() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Chainbridge PendingProposals (r:1 w:1)
+	// Storage: Chainbridge InboundContext (r:0 w:2)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge InFlightProposals (r:0 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:0 w:1)
+	fn approve_pending() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Chainbridge BridgePaused (r:0 w:1)
+	fn pause_bridge() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge BridgePaused (r:0 w:1)
+	fn unpause_bridge() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge PausedChains (r:0 w:1)
+	fn pause_chain() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge PausedChains (r:0 w:1)
+	fn unpause_chain() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Chainbridge Relayers (r:1 w:0)
+	// Storage: Chainbridge Chains (r:1 w:0)
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Resources (r:1 w:0)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:1 w:1)
+	// Storage: Chainbridge InFlightProposals (r:1 w:1)
+	// Storage: Chainbridge RelayerVotes (r:1 w:1)
+	// Storage: Chainbridge Equivocations (r:1 w:1)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge PendingRewards (r:v w:v)
+	// Storage: Chainbridge RelayerBonds (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:0)
+	// Storage: Chainbridge RelayerSince (r:v w:0)
+	fn acknowledge_proposal(v: u32, ) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((310_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: Chainbridge Relayers (r:1 w:0)
+	// Storage: Chainbridge Chains (r:1 w:0)
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Resources (r:1 w:0)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:1 w:1)
+	// Storage: Chainbridge InFlightProposals (r:1 w:1)
+	// Storage: Chainbridge RelayerVotes (r:0 w:1)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge PendingRewards (r:v w:v)
+	// Storage: Chainbridge RelayerBonds (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:0)
+	// Storage: Chainbridge RelayerSince (r:v w:0)
+	fn reject_proposal(v: u32, ) -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add((305_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:1 w:1)
+	// Storage: Chainbridge InFlightProposals (r:1 w:1)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge PendingRewards (r:v w:v)
+	// Storage: Chainbridge RelayerBonds (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:0)
+	// Storage: Chainbridge RelayerSince (r:v w:0)
+	fn eval_vote_state(v: u32, ) -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: Chainbridge Relayers (r:1 w:0)
+	// Storage: Chainbridge Chains (r:1 w:0)
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Resources (r:1 w:0)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:1 w:0)
+	// Storage: Chainbridge InFlightProposals (r:1 w:1)
+	// Storage: Chainbridge RelayerVotes (r:1 w:1)
+	// Storage: Chainbridge Equivocations (r:1 w:1)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge AwaitingPreimage (r:0 w:1)
+	// Storage: Chainbridge PendingRewards (r:v w:v)
+	// Storage: Chainbridge RelayerBonds (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:0)
+	// Storage: Chainbridge RelayerSince (r:v w:0)
+	fn acknowledge_proposal_hash(v: u32, ) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((308_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: Chainbridge Relayers (r:1 w:0)
+	// Storage: Chainbridge Chains (r:1 w:0)
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Resources (r:1 w:0)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:1 w:0)
+	// Storage: Chainbridge InFlightProposals (r:1 w:1)
+	// Storage: Chainbridge RelayerVotes (r:0 w:1)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge AwaitingPreimage (r:0 w:1)
+	// Storage: Chainbridge PendingRewards (r:v w:v)
+	// Storage: Chainbridge RelayerBonds (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:0)
+	// Storage: Chainbridge RelayerSince (r:v w:0)
+	fn reject_proposal_hash(v: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((303_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Resources (r:1 w:0)
+	// Storage: Chainbridge AwaitingPreimage (r:1 w:1)
+	// Storage: Chainbridge InboundContext (r:0 w:2)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge InFlightProposals (r:0 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:0 w:1)
+	fn execute_proposal() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Chainbridge Chains (r:1 w:0)
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Resources (r:1 w:0)
+	// Storage: Chainbridge ExecutedNonces (r:1 w:1)
+	// Storage: Chainbridge InFlightProposals (r:1 w:1)
+	// Storage: Chainbridge Relayers (r:a w:0)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge PendingRewards (r:a w:a)
+	// Storage: Chainbridge PruneQueue (r:1 w:1)
+	// Storage: Chainbridge Votes (r:0 w:1)
+	// Storage: Chainbridge InboundContext (r:0 w:2)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:0)
+	fn submit_attested_proposal(a: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((49_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: Chainbridge Relayers (r:1 w:0)
+	// Storage: Chainbridge Chains (r:1 w:0)
+	// Storage: Chainbridge BridgePaused (r:1 w:0)
+	// Storage: Chainbridge PausedChains (r:1 w:0)
+	// Storage: Chainbridge Resources (r:1 w:0)
+	// Storage: Chainbridge Votes (r:1 w:1)
+	// Storage: Chainbridge ExecutedNonces (r:1 w:0)
+	// Storage: Chainbridge InFlightProposals (r:1 w:1)
+	// Storage: Chainbridge RelayerVotes (r:1 w:1)
+	// Storage: Chainbridge Equivocations (r:1 w:1)
+	// Storage: Chainbridge RelayerThreshold (r:1 w:0)
+	// Storage: Chainbridge RelayerThresholdRatio (r:1 w:0)
+	// Storage: Chainbridge RelayerCount (r:1 w:0)
+	// Storage: Chainbridge AwaitingPreimage (r:0 w:1)
+	// Storage: Chainbridge PendingRewards (r:v w:v)
+	// Storage: Chainbridge RelayerBonds (r:v w:v)
+	// Storage: System Account (r:v w:v)
+	// Storage: Chainbridge RelayerEpoch (r:1 w:0)
+	// Storage: Chainbridge RelayerSince (r:v w:0)
+	fn submit_vote_unsigned(v: u32, ) -> Weight {
+		(92_000_000 as Weight)
+			.saturating_add((308_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: Chainbridge NextPruneBlock (r:1 w:1)
+	// Storage: Chainbridge PruneQueue (r:1 w:1)
+	// Storage: Chainbridge Votes (r:n w:n)
+	// Storage: Chainbridge AwaitingPreimage (r:n w:n)
+	// Storage: Chainbridge InFlightProposals (r:n w:n)
+	// Storage: Chainbridge RelayerVotes (r:0 w:31n)
+	// Storage: Chainbridge RejectedVotes (r:0 w:32n)
+	fn on_idle(n: u32, ) -> Weight {
+		(6_000_000 as Weight)
+			.saturating_add((154_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((67 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set_threshold() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_threshold_ratio() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_resource() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_resource() -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn whitelist_chain() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn register_chain() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn disable_chain() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn enable_chain() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn add_relayer() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn remove_relayer() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn set_fee() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn claim_rewards() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn withdraw_unbonded() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_transfer_limit() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_inbound_limit() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn retry_pending() -> Weight {
+		(29_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn approve_pending() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn pause_bridge() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unpause_bridge() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn pause_chain() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn unpause_chain() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn acknowledge_proposal(v: u32, ) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((310_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn reject_proposal(v: u32, ) -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add((305_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn eval_vote_state(v: u32, ) -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add((190_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn acknowledge_proposal_hash(v: u32, ) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((308_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn reject_proposal_hash(v: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((303_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn execute_proposal() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn submit_attested_proposal(a: u32, ) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add((49_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
+	}
+	fn submit_vote_unsigned(v: u32, ) -> Weight {
+		(92_000_000 as Weight)
+			.saturating_add((308_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn on_idle(n: u32, ) -> Weight {
+		(6_000_000 as Weight)
+			.saturating_add((154_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((67 as Weight).saturating_mul(n as Weight)))
+	}
+}
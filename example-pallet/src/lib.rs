@@ -26,10 +26,12 @@ mod types {
 pub mod pallet {
     use crate::types::BalanceOf;
     use crate::types::ResourceId;
+    use chainbridge::InboundVolume;
     use frame_support::pallet_prelude::*;
     use frame_support::sp_runtime::SaturatedConversion;
     use frame_support::traits::Currency;
     use frame_support::traits::ExistenceRequirement::AllowDeath;
+    use frame_support::transactional;
     use frame_system::pallet_prelude::*;
     use sp_core::U256;
     use sp_std::vec::Vec;
@@ -90,11 +92,12 @@ pub mod pallet {
             hash: T::Hash,
             dest_id: chainbridge::ChainId,
         ) -> DispatchResult {
-            ensure_signed(origin)?;
+            let source = ensure_signed(origin)?;
 
             let resource_id = T::HashId::get();
             let metadata: Vec<u8> = hash.as_ref().to_vec();
             <chainbridge::Pallet<T>>::transfer_generic(
+                source,
                 dest_id,
                 resource_id,
                 metadata,
@@ -106,6 +109,7 @@ pub mod pallet {
         /// Transfer some amount of the native token to some recipient on a (whitelisted)
         /// destination chain.
         #[pallet::weight(10_000)]
+        #[transactional]
         pub fn transfer_native(
             origin: OriginFor<T>,
             amount: BalanceOf<T>,
@@ -119,7 +123,7 @@ pub mod pallet {
             );
 
             let bridge_id = <chainbridge::Pallet<T>>::account_id();
-            T::Currency::transfer(
+            <T as Config>::Currency::transfer(
                 &source,
                 &bridge_id,
                 amount.into(),
@@ -128,6 +132,7 @@ pub mod pallet {
 
             let resource_id = T::NativeTokenId::get();
             <chainbridge::Pallet<T>>::transfer_fungible(
+                source,
                 dest_id,
                 resource_id,
                 recipient,
@@ -138,6 +143,7 @@ pub mod pallet {
 
         /// Transfer a non-fungible token (erc721) to a (whitelisted) destination chain.
         #[pallet::weight(10_000)]
+        #[transactional]
         pub fn transfer_erc721(
             origin: OriginFor<T>,
             recipient: Vec<u8>,
@@ -152,12 +158,14 @@ pub mod pallet {
             match <pallet_example_erc721::Pallet<T>>::tokens(&token_id) {
                 Some(token) => {
                     <pallet_example_erc721::Pallet<T>>::burn_token(
-                        source, token_id,
+                        source.clone(),
+                        token_id,
                     )?;
                     let resource_id = T::Erc721Id::get();
                     let tid: &mut [u8] = &mut [0; 32];
                     token_id.to_big_endian(tid);
                     <chainbridge::Pallet<T>>::transfer_nonfungible(
+                        source,
                         dest_id,
                         resource_id,
                         tid.to_vec(),
@@ -182,6 +190,9 @@ pub mod pallet {
             _r_id: ResourceId,
         ) -> DispatchResult {
             let source = T::BridgeOrigin::ensure_origin(origin)?;
+            <chainbridge::Pallet<T>>::note_inbound(U256::from(
+                amount.saturated_into::<u128>(),
+            ))?;
             <T as Config>::Currency::transfer(
                 &source,
                 &to,
@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, ChainbridgeConfig, GenesisConfig, GrandpaConfig, HashId,
+	NFTTokenId, NativeTokenId, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -59,6 +59,10 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Bridge relayers
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+				// Bridge relayer threshold
+				1,
 				true,
 			)
 		},
@@ -106,6 +110,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Bridge relayers
+				vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				],
+				// Bridge relayer threshold
+				2,
 				true,
 			)
 		},
@@ -128,6 +140,8 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	bridge_relayers: Vec<AccountId>,
+	bridge_threshold: u32,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -150,5 +164,16 @@ fn testnet_genesis(
 			key: root_key,
 		},
 		transaction_payment: Default::default(),
+		chainbridge: ChainbridgeConfig {
+			relayers: bridge_relayers,
+			threshold: bridge_threshold,
+			// Chains 0 and 1 are the origin chains of the resources below.
+			chains: vec![(0, 0), (1, 0)],
+			resources: vec![
+				(HashId::get(), b"Example.remark".to_vec()),
+				(NativeTokenId::get(), b"Example.transfer".to_vec()),
+				(NFTTokenId::get(), b"Example.mint_erc721".to_vec()),
+			],
+		},
 	}
 }
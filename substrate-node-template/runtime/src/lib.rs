@@ -16,7 +16,9 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{
+		TransactionLongevity, TransactionPriority, TransactionSource, TransactionValidity,
+	},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -28,7 +30,7 @@ use sp_version::RuntimeVersion;
 use frame_support::PalletId;
 pub use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+	traits::{Contains, KeyOwnerProofSystem, Randomness, StorageInfo},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
@@ -104,10 +106,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -288,16 +290,109 @@ impl pallet_template::Config for Runtime {
 parameter_types! {
 	pub const ChainId: u8 = 5;
 	pub const ProposalLifetime: BlockNumber = 1000;
+	pub const ProposalRetention: BlockNumber = DAYS;
+	pub const PendingLifetime: BlockNumber = 7 * DAYS;
+	pub const MaxRelayers: u32 = 32;
+	pub const MaxResourceMethodLen: u32 = 64;
+	pub const MaxRecipientLen: u32 = 64;
+	pub const MaxMetadataLen: u32 = 4 * 1024;
+	pub const MaxChainNameLen: u32 = 32;
+	pub const MaxBatchSize: u32 = 64;
+	pub const MaxPrunePerBlock: u32 = 256;
+	pub const BridgeUnsignedPriority: TransactionPriority = TransactionPriority::MAX / 2;
+	pub const BridgeUnsignedLongevity: TransactionLongevity = 64;
+	pub const RelayerReward: Balance = 5_000;
+	pub const RelayerBond: Balance = 500_000;
+	pub const RelayerSlashFraction: Perbill = Perbill::from_percent(10);
+	pub const UnbondingPeriod: BlockNumber = 7 * DAYS;
 	pub const ChainBridgePalletId: PalletId = PalletId(*b"chnbrdge");
 }
 
+/// Calls the bridge account may execute as the result of a proposal
+pub struct BridgeProposalFilter;
+impl Contains<Call> for BridgeProposalFilter {
+	fn contains(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Example(pallet_example::Call::transfer { .. }) |
+				Call::Example(pallet_example::Call::remark { .. }) |
+				Call::Example(pallet_example::Call::mint_erc721 { .. })
+		)
+	}
+}
+
+/// Signs chainbridge attestations in benchmarks with sr25519 keys generated in
+/// the benchmark keystore, and proposes remarks of the example pallet
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BridgeBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl chainbridge::BenchmarkHelper<AccountId, Signature, Call> for BridgeBenchmarkHelper {
+	fn create_signer(_index: u32) -> AccountId {
+		let public = sp_io::crypto::sr25519_generate(BRIDGE_BENCHMARK_KEY, None);
+		<Signature as Verify>::Signer::from(public).into_account()
+	}
+
+	fn sign(signer: &AccountId, message: &[u8]) -> Signature {
+		let public = sp_core::sr25519::Public::from_raw(signer.clone().into());
+		sp_io::crypto::sr25519_sign(BRIDGE_BENCHMARK_KEY, &public, message)
+			.expect("key was generated in the keystore; qed")
+			.into()
+	}
+
+	fn make_proposal() -> Call {
+		Call::Example(pallet_example::Call::remark {
+			hash: Default::default(),
+			r_id: Default::default(),
+		})
+	}
+
+	fn proposal_method() -> Vec<u8> {
+		b"Example.remark".to_vec()
+	}
+}
+
+/// Key type of the relayer keys generated for the chainbridge benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+const BRIDGE_BENCHMARK_KEY: KeyTypeId = KeyTypeId(*b"cbrb");
+
 impl chainbridge::Config for Runtime {
 	type Event = Event;
 	type ChainId = ChainId;
 	type AdminOrigin = EnsureRoot<Self::AccountId>;
+	type PauseOrigin = EnsureRoot<Self::AccountId>;
 	type Proposal = Call;
 	type ProposalLifetime = ProposalLifetime;
+	type ProposalRetention = ProposalRetention;
+	type PendingLifetime = PendingLifetime;
+	type MaxRelayers = MaxRelayers;
+	type MaxResourceMethodLen = MaxResourceMethodLen;
+	type MaxRecipientLen = MaxRecipientLen;
+	type MaxMetadataLen = MaxMetadataLen;
+	type MaxChainNameLen = MaxChainNameLen;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxPrunePerBlock = MaxPrunePerBlock;
+	type Currency = Balances;
+	type FeeHandler = chainbridge::FeePot<Runtime>;
+	type RelayerReward = RelayerReward;
+	type RelayerBond = RelayerBond;
+	type SlashFraction = RelayerSlashFraction;
+	type Slash = ();
+	type UnbondingPeriod = UnbondingPeriod;
+	type OnEquivocation = (
+		chainbridge::SlashEquivocator<Runtime>,
+		chainbridge::RemoveEquivocator<Runtime>,
+	);
+	type ResourceCallMatcher = chainbridge::MatchCallName;
+	type ProposalFilter = BridgeProposalFilter;
+	type RelayerSignature = Signature;
+	type RelayerPublic = <Signature as Verify>::Signer;
+	type UnsignedPriority = BridgeUnsignedPriority;
+	type UnsignedLongevity = BridgeUnsignedLongevity;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BridgeBenchmarkHelper;
 	type PalletId = ChainBridgePalletId;
+	type WeightInfo = chainbridge::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -380,6 +475,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	chainbridge::CheckRelayerVote<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -541,6 +637,7 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_balances, Balances);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, chainbridge, Chainbridge);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -579,6 +676,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, chainbridge, Chainbridge);
 
 			Ok(batches)
 		}